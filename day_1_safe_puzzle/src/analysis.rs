@@ -0,0 +1,812 @@
+use crate::command::{Direction, RotationCommand};
+use crate::dial::{
+    CountMode, DEFAULT_SIZE, DEFAULT_START, Position, SafeDialKnob, crossings_for, turn,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Yields the starting position followed by the resting position after each command,
+/// using solution-one semantics.
+fn resting_positions(commands: &[RotationCommand]) -> impl Iterator<Item = i32> + '_ {
+    std::iter::once(DEFAULT_START)
+        .chain(walk(DEFAULT_START, commands).map(|(_, position)| position))
+}
+
+/// Lazily pairs each command with the resting position it leaves the dial at, starting
+/// from `start` and using solution-one semantics.
+pub fn walk<'a>(
+    start: i32,
+    commands: &'a [RotationCommand],
+) -> impl Iterator<Item = (&'a RotationCommand, i32)> + 'a {
+    let mut knob = SafeDialKnob::at_position(start);
+    commands.iter().map(move |command| {
+        knob.rotate_knob_solution_one(command);
+        (command, knob.current_position())
+    })
+}
+
+/// Returns the index of the first command after which the dial rests on `target`, starting
+/// from the default position.
+pub fn first_landing_on(commands: &[RotationCommand], target: i32) -> Option<usize> {
+    walk(DEFAULT_START, commands).position(|(_, position)| position == target)
+}
+
+/// Renders the run as CSV with one row per command: its index, the command, the resting
+/// position it leaves and the solution-two zero events it caused.
+///
+/// # Panics
+///
+/// Panics on a negative distance, like [`crossings_for`].
+pub fn trace_csv(commands: &[RotationCommand]) -> String {
+    let mut csv = String::from("index,command,resting_position,zeros_this_command\n");
+    let mut previous = DEFAULT_START;
+    for (index, (command, position)) in walk(DEFAULT_START, commands).enumerate() {
+        let zeros = crossings_for(previous, command, DEFAULT_SIZE);
+        writeln!(csv, "{index},{command},{position},{zeros}").expect("writing to a String");
+        previous = position;
+    }
+    csv
+}
+
+/// Lists every solution-two zero click as the index of the command it happened in and the
+/// position the dial pointed at before that command started. Its length is the solution-two
+/// answer.
+///
+/// # Panics
+///
+/// Panics on a negative distance, like [`crossings_for`].
+pub fn crossing_contexts(commands: &[RotationCommand]) -> Vec<(usize, i32)> {
+    let mut contexts = Vec::new();
+    let mut previous = DEFAULT_START;
+    for (index, (command, position)) in walk(DEFAULT_START, commands).enumerate() {
+        let crossings = crossings_for(previous, command, DEFAULT_SIZE) as usize;
+        contexts.extend(std::iter::repeat_n((index, previous), crossings));
+        previous = position;
+    }
+    contexts
+}
+
+/// Splits `commands` into maximal runs of the same direction and returns each run's
+/// direction with its length.
+pub fn direction_runs(commands: &[RotationCommand]) -> Vec<(Direction, usize)> {
+    commands
+        .chunk_by(|a, b| a.direction == b.direction)
+        .map(|run| (run[0].direction, run.len()))
+        .collect()
+}
+
+/// Sums the distances of all left and right turns. Absolute jumps hold a target position
+/// rather than a distance and are left out.
+pub fn total_distance(commands: &[RotationCommand]) -> i64 {
+    commands
+        .iter()
+        .filter_map(RotationCommand::turn_distance)
+        .map(i64::from)
+        .sum()
+}
+
+/// Number of clicks solution two steps through, i.e. the sum of absolute distances, with an
+/// absolute jump counting as one click. Summed as `u64` so it cannot overflow.
+pub fn total_clicks(commands: &[RotationCommand]) -> u64 {
+    commands
+        .iter()
+        .map(|command| {
+            command
+                .turn_distance()
+                .map_or(1, |distance| u64::from(distance.unsigned_abs()))
+        })
+        .sum()
+}
+
+/// Indices of commands that exactly repeat the command right before them, which usually
+/// points at a line pasted twice.
+pub fn consecutive_duplicates(commands: &[RotationCommand]) -> Vec<usize> {
+    commands
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] == pair[1])
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+/// Indices of turns whose distance is a whole number of revolutions of a dial with `size`
+/// positions, including zero-distance turns. They rest where they started, so solution one
+/// sees nothing, but solution two still counts the zero clicks they pass. Absolute jumps are
+/// never flagged.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn full_loop_commands(commands: &[RotationCommand], size: u32) -> Vec<usize> {
+    commands
+        .iter()
+        .enumerate()
+        .filter(|(_, command)| {
+            command
+                .turn_distance()
+                .is_some_and(|distance| i64::from(distance) % i64::from(size) == 0)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The largest distance of any left or right turn, or 0 when there is none. Absolute jumps
+/// are ignored.
+pub fn max_distance(commands: &[RotationCommand]) -> i32 {
+    commands
+        .iter()
+        .filter_map(RotationCommand::turn_distance)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Whether every turn distance is non-negative and fits in a `u16`. The targets of absolute
+/// jumps are positions, not distances, and are not checked.
+pub fn fits_u16(commands: &[RotationCommand]) -> bool {
+    commands
+        .iter()
+        .filter_map(RotationCommand::turn_distance)
+        .all(|distance| u16::try_from(distance).is_ok())
+}
+
+/// Returns the lowest and highest resting positions of a run, including the start.
+pub fn position_bounds(commands: &[RotationCommand]) -> (i32, i32) {
+    resting_positions(commands).fold((i32::MAX, i32::MIN), |(min, max), position| {
+        (min.min(position), max.max(position))
+    })
+}
+
+/// Counts the unique resting positions of a run, including the start.
+pub fn distinct_positions(commands: &[RotationCommand]) -> usize {
+    resting_positions(commands).collect::<HashSet<_>>().len()
+}
+
+/// Lists the distinct resting positions of a run in the order they are first reached,
+/// starting with the start.
+pub fn first_visit_order(commands: &[RotationCommand]) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    resting_positions(commands)
+        .filter(|&position| seen.insert(position))
+        .collect()
+}
+
+/// Lists, in ascending order, the positions of the dial that are never a resting position
+/// of the run. The start counts as visited.
+pub fn unvisited_positions(commands: &[RotationCommand]) -> Vec<i32> {
+    let visited: HashSet<i32> = resting_positions(commands).collect();
+    (0..DEFAULT_SIZE as i32)
+        .filter(|position| !visited.contains(position))
+        .collect()
+}
+
+/// Finds the first resting position that repeats and returns `(start, length)`, where `start`
+/// is the index of its first visit in the resting-position stream (index 0 is the start).
+pub fn detect_cycle(commands: &[RotationCommand]) -> Option<(usize, usize)> {
+    let mut first_seen: HashMap<i32, usize> = HashMap::new();
+    for (index, position) in resting_positions(commands).enumerate() {
+        if let Some(&start) = first_seen.get(&position) {
+            return Some((start, index - start));
+        }
+        first_seen.insert(position, index);
+    }
+    None
+}
+
+/// Runs only `commands`, starting from `start_position`, and returns the ending position
+/// together with the zero events counted in that segment.
+///
+/// # Panics
+///
+/// Panics on a negative distance in [`CountMode::Crossings`].
+pub fn run_window(
+    start_position: i32,
+    commands: &[RotationCommand],
+    mode: CountMode,
+) -> (i32, u32) {
+    let mut knob = SafeDialKnob::at_position(start_position);
+    commands
+        .iter()
+        .for_each(|command| knob.rotate(command, mode));
+    (knob.current_position(), knob.get_code_sequence())
+}
+
+/// Runs `commands` from `start` until the first command that produces a zero event under
+/// `mode` and returns its index with the position the dial rests at after it. When no
+/// command produces one, returns `commands.len()` with the final position.
+///
+/// # Panics
+///
+/// Panics on a negative distance in [`CountMode::Crossings`].
+pub fn run_until_first_zero(
+    start: i32,
+    commands: &[RotationCommand],
+    mode: CountMode,
+) -> (usize, i32) {
+    let mut knob = SafeDialKnob::at_position(start);
+    for (index, command) in commands.iter().enumerate() {
+        knob.rotate(command, mode);
+        if knob.zero_count() > 0 {
+            return (index, knob.current_position());
+        }
+    }
+    (commands.len(), knob.current_position())
+}
+
+/// Runs `commands` from `start` with solution-one semantics until `stop` accepts the
+/// position the dial rests at, and returns that command's index with the position. The
+/// start itself is not checked. When `stop` never accepts, returns `commands.len()` with the
+/// final position, like [`run_until_first_zero`].
+pub fn run_until<F: FnMut(i32) -> bool>(
+    start: i32,
+    commands: &[RotationCommand],
+    mut stop: F,
+) -> (usize, i32) {
+    let mut position = i32::from(Position::wrapping(start));
+    for (index, (_, resting)) in walk(start, commands).enumerate() {
+        position = resting;
+        if stop(position) {
+            return (index, position);
+        }
+    }
+    (commands.len(), position)
+}
+
+/// Runs `commands` on a fresh dial of `size` positions pointing at `start` and returns the
+/// ending position together with the zero events counted. A `start` outside `0..size` is
+/// wrapped onto the dial like the builder does, so `simulate(10, 15, ..)` starts at 5.
+///
+/// # Panics
+///
+/// Panics if `size` is 0 or too large for an `i32`, or on a negative distance in
+/// [`CountMode::Crossings`].
+pub fn simulate(
+    size: u32,
+    start: i32,
+    commands: &[RotationCommand],
+    mode: CountMode,
+) -> (i32, u32) {
+    let knob = SafeDialKnob::builder()
+        .size(size)
+        .start(start)
+        .build()
+        .expect("simulated dial is valid")
+        .apply(commands, mode);
+    (knob.current_position(), knob.zero_count())
+}
+
+/// Counts the zero clicks only solution two sees, i.e. the ones passed over in the middle of
+/// a rotation rather than rested on.
+///
+/// A zero-distance command resting on 0 is a landing without a click, so inputs made mostly
+/// of those saturate at 0 instead of going negative. Fails like
+/// [`SafeDialKnob::count_zero_crossings`] on a rotation that would overflow.
+pub fn extra_crossings(commands: &[RotationCommand]) -> anyhow::Result<u32> {
+    Ok(SafeDialKnob::count_zero_crossings(commands)?
+        .saturating_sub(SafeDialKnob::count_zero_landings(commands)))
+}
+
+/// Reverses `commands` and flips each turn, so running the result after the original
+/// returns the dial to where it started. An absolute jump is undone by jumping back to the
+/// position it left, as reached from the default start.
+pub fn reverse_sequence(commands: &[RotationCommand]) -> Vec<RotationCommand> {
+    let departures: Vec<i32> = resting_positions(commands).collect();
+    commands
+        .iter()
+        .zip(departures)
+        .rev()
+        .map(|(command, departure)| match command.direction.opposite() {
+            Some(direction) => RotationCommand {
+                direction,
+                distance: command.distance,
+            },
+            None => RotationCommand {
+                direction: Direction::Absolute,
+                distance: departure,
+            },
+        })
+        .collect()
+}
+
+/// Lists the position after every click of a run from the default start, using
+/// solution-two stepping. An absolute jump is one click.
+///
+/// # Panics
+///
+/// Panics on a negative distance, which has no clicks.
+pub fn clicks(commands: &[RotationCommand]) -> Vec<i32> {
+    let mut position = DEFAULT_START;
+    let mut positions = Vec::new();
+    for command in commands {
+        if command.direction == Direction::Absolute {
+            position = turn(
+                position,
+                &command.direction,
+                command.distance,
+                DEFAULT_SIZE as i32,
+            );
+            positions.push(position);
+            continue;
+        }
+        assert!(
+            command.distance >= 0,
+            "negative distance in '{command}' has no solution-two clicks"
+        );
+        for _ in 0..command.distance {
+            position = turn(position, &command.direction, 1, DEFAULT_SIZE as i32);
+            positions.push(position);
+        }
+    }
+    positions
+}
+
+/// Returns the cumulative click count, across all commands, at every click that points the
+/// dial at 0. Its length is the solution-two answer.
+///
+/// # Panics
+///
+/// Panics on a negative distance, like [`clicks`].
+pub fn zero_crossing_clicks(commands: &[RotationCommand]) -> Vec<u64> {
+    clicks(commands)
+        .iter()
+        .zip(1..)
+        .filter(|&(&position, _)| position == 0)
+        .map(|(_, click)| click)
+        .collect()
+}
+
+/// Index of the first command after which `a` and `b` leave the dial resting at different
+/// positions, both starting from the default start. When the shorter input runs out before
+/// any difference, its length is returned; identical traces give `None`.
+pub fn first_divergence(a: &[RotationCommand], b: &[RotationCommand]) -> Option<usize> {
+    let mut a_positions = walk(DEFAULT_START, a).map(|(_, position)| position);
+    let mut b_positions = walk(DEFAULT_START, b).map(|(_, position)| position);
+    (0..).find_map(|index| match (a_positions.next(), b_positions.next()) {
+        (None, None) => Some(None),
+        (a_position, b_position) if a_position != b_position => Some(Some(index)),
+        _ => None,
+    })?
+}
+
+/// A shortest command list whose solution-one answer from the default start is exactly
+/// `target_count`: one hop onto 0, then a full turn back onto 0 for every further landing.
+/// Each command lands on 0 once, so no shorter list can reach the count.
+pub fn synthesize_for_count(target_count: u32) -> Vec<RotationCommand> {
+    let hop = RotationCommand {
+        direction: Direction::Right,
+        distance: DEFAULT_SIZE as i32 - DEFAULT_START,
+    };
+    let full_turns = (1..target_count).map(|_| RotationCommand {
+        direction: Direction::Right,
+        distance: DEFAULT_SIZE as i32,
+    });
+    (target_count > 0)
+        .then_some(hop)
+        .into_iter()
+        .chain(full_turns)
+        .collect()
+}
+
+/// Counts the solution-two zero clicks whose click number falls in `a..b`, numbering clicks
+/// from 1 like [`zero_crossing_clicks`] does, so `a..a + 1` holds just click `a`. An empty or
+/// reversed range counts nothing. Each command is counted with [`crossings_for`] rather than
+/// stepped click by click.
+///
+/// # Panics
+///
+/// Panics on a negative distance, like [`crossings_for`].
+pub fn zeros_in_click_range(commands: &[RotationCommand], a: u64, b: u64) -> u32 {
+    let mut position = DEFAULT_START;
+    let mut offset = 0;
+    let mut zeros = 0;
+    for command in commands {
+        if offset + 1 >= b {
+            break;
+        }
+        // Like `clicks`, an absolute jump is one click.
+        let length = match command.direction {
+            Direction::Absolute => 1,
+            _ => u64::try_from(command.distance).unwrap_or_else(|_| {
+                panic!("negative distance in '{command}' has no solution-two clicks")
+            }),
+        };
+        if length == 0 {
+            continue;
+        }
+
+        let zeros_through = |click: u64| match click {
+            0 => 0,
+            click if click == length => crossings_for(position, command, DEFAULT_SIZE),
+            click => {
+                let partial = RotationCommand {
+                    distance: click as i32,
+                    ..*command
+                };
+                crossings_for(position, &partial, DEFAULT_SIZE)
+            }
+        };
+        let first = a.saturating_sub(offset).max(1);
+        let last = b.saturating_sub(offset + 1).min(length);
+        if first <= last {
+            zeros += zeros_through(last) - zeros_through(first - 1);
+        }
+
+        position = turn(
+            position,
+            &command.direction,
+            command.distance,
+            DEFAULT_SIZE as i32,
+        );
+        offset += length;
+    }
+    zeros
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{parse_commands, sample_commands};
+
+    #[test]
+    fn test_direction_runs() {
+        let commands = parse_commands("R1\nR2\nL3\nR4\n").unwrap();
+
+        assert_eq!(
+            direction_runs(&commands),
+            [
+                (Direction::Right, 2),
+                (Direction::Left, 1),
+                (Direction::Right, 1)
+            ]
+        );
+        assert_eq!(direction_runs(&sample_commands()).len(), 7);
+        assert!(direction_runs(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_total_distance() {
+        assert_eq!(total_distance(&sample_commands()), 462);
+        assert_eq!(total_distance(&[]), 0);
+        assert_eq!(total_distance(&parse_commands("A99\nR5\n").unwrap()), 5);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let a = parse_commands("L68\nL30\nR48\nL5\n").unwrap();
+        let b = parse_commands("L68\nR70\nR49\nL5\n").unwrap();
+
+        assert_eq!(first_divergence(&a, &b), Some(2));
+        assert_eq!(first_divergence(&a, &a), None);
+        assert_eq!(first_divergence(&a, &a[..3]), Some(3));
+        assert_eq!(first_divergence(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_synthesize_for_count() {
+        let commands = synthesize_for_count(3);
+        assert_eq!(commands.len(), 3);
+        assert_eq!(SafeDialKnob::count_zero_landings(&commands), 3);
+        assert_eq!(commands, parse_commands("R50\nR100\nR100\n").unwrap());
+
+        assert!(synthesize_for_count(0).is_empty());
+        assert_eq!(
+            SafeDialKnob::count_zero_landings(&synthesize_for_count(1)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_consecutive_duplicates() {
+        let commands = parse_commands("L68\nL30\nL30\nR30\nL30\nR48\nR48\nR48\n").unwrap();
+        assert_eq!(consecutive_duplicates(&commands), [2, 6, 7]);
+        assert!(consecutive_duplicates(&sample_commands()).is_empty());
+        assert!(consecutive_duplicates(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_full_loop_commands() {
+        let commands = parse_commands("L68\nR100\nL300\nR0\nA0\nR50\n").unwrap();
+        assert_eq!(full_loop_commands(&commands, 100), [1, 2, 3]);
+        assert_eq!(full_loop_commands(&commands, 50), [1, 2, 3, 5]);
+        assert!(full_loop_commands(&sample_commands(), 100).is_empty());
+    }
+
+    #[test]
+    fn test_total_clicks() {
+        let test_puzzle_input = sample_commands();
+        assert_eq!(total_clicks(&test_puzzle_input), 462);
+        assert_eq!(
+            total_clicks(&test_puzzle_input),
+            clicks(&test_puzzle_input).len() as u64
+        );
+
+        let large = parse_commands(
+            "R2147483647
+L-2147483647
+A5
+",
+        )
+        .unwrap();
+        assert_eq!(total_clicks(&large), 2 * 2_147_483_647 + 1);
+    }
+
+    #[test]
+    fn test_max_distance_and_fits_u16() {
+        let test_puzzle_input = sample_commands();
+        assert_eq!(max_distance(&test_puzzle_input), 99);
+        assert!(fits_u16(&test_puzzle_input));
+
+        let large = [RotationCommand::parse("R65536").unwrap()];
+        assert_eq!(max_distance(&large), 65536);
+        assert!(!fits_u16(&large));
+        assert!(!fits_u16(&[RotationCommand::parse("R-1").unwrap()]));
+        assert_eq!(max_distance(&[]), 0);
+
+        let absolute = parse_commands("A70000\nL5\n").unwrap();
+        assert_eq!(max_distance(&absolute), 5);
+        assert!(fits_u16(&absolute));
+    }
+
+    #[test]
+    fn test_position_bounds() {
+        let test_puzzle_input = sample_commands();
+
+        assert_eq!(position_bounds(&test_puzzle_input), (0, 99));
+        assert_eq!(position_bounds(&test_puzzle_input[..2]), (50, 82));
+        assert_eq!(position_bounds(&[]), (50, 50));
+    }
+
+    #[test]
+    fn test_distinct_positions() {
+        let test_puzzle_input = sample_commands();
+        let expected: HashSet<i32> = [50, 82, 52, 0, 95, 55, 99, 14, 32].into();
+
+        assert_eq!(distinct_positions(&test_puzzle_input), expected.len());
+        assert_eq!(distinct_positions(&[]), 1);
+    }
+
+    #[test]
+    fn test_first_visit_order() {
+        assert_eq!(
+            first_visit_order(&sample_commands()),
+            [50, 82, 52, 0, 95, 55, 99, 14, 32]
+        );
+        assert_eq!(first_visit_order(&[]), [50]);
+    }
+
+    #[test]
+    fn test_unvisited_positions() {
+        let unvisited = unvisited_positions(&sample_commands());
+
+        assert_eq!(
+            unvisited.len(),
+            100 - distinct_positions(&sample_commands())
+        );
+        for position in [1, 33, 51, 98] {
+            assert!(unvisited.contains(&position));
+        }
+        for position in [0, 32, 50, 99] {
+            assert!(!unvisited.contains(&position));
+        }
+        assert!(unvisited.is_sorted());
+    }
+
+    #[test]
+    fn test_walk() {
+        let test_puzzle_input = sample_commands();
+        let steps: Vec<(&RotationCommand, i32)> = walk(50, &test_puzzle_input).collect();
+
+        assert_eq!(steps.len(), 10);
+        let (command, position) = steps[2];
+        assert_eq!(command.to_string(), "R48");
+        assert_eq!(position, 0);
+        assert_eq!(steps.last().unwrap().1, 32);
+    }
+
+    #[test]
+    fn test_first_landing_on() {
+        let test_puzzle_input = sample_commands();
+
+        assert_eq!(first_landing_on(&test_puzzle_input, 0), Some(2));
+        assert_eq!(first_landing_on(&test_puzzle_input, 32), Some(9));
+        assert_eq!(first_landing_on(&test_puzzle_input, 1), None);
+        assert_eq!(first_landing_on(&test_puzzle_input, 50), None);
+    }
+
+    #[test]
+    fn test_trace_csv() {
+        let csv = trace_csv(&sample_commands());
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 11);
+        assert_eq!(rows[0], "index,command,resting_position,zeros_this_command");
+        assert_eq!(rows[1], "0,L68,82,1");
+        assert_eq!(rows[3], "2,R48,0,1");
+        let total: u32 = rows[1..]
+            .iter()
+            .map(|row| row.rsplit(',').next().unwrap().parse::<u32>().unwrap())
+            .sum();
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn test_crossing_contexts() {
+        let contexts = crossing_contexts(&sample_commands());
+
+        assert_eq!(contexts.len(), 6);
+        assert_eq!(contexts[0], (0, 50));
+        assert_eq!(contexts[1], (2, 52));
+
+        let r1000 = [RotationCommand::parse("R1000").unwrap()];
+        assert_eq!(crossing_contexts(&r1000), vec![(0, 50); 10]);
+    }
+
+    #[test]
+    fn test_negative_distance_has_no_solution_two_clicks() {
+        let commands = parse_commands("L47\nR-5\n").unwrap();
+
+        assert!(SafeDialKnob::count_zero_crossings(&commands).is_err());
+        assert!(std::panic::catch_unwind(|| crossing_contexts(&commands)).is_err());
+        assert!(std::panic::catch_unwind(|| trace_csv(&commands)).is_err());
+        assert!(std::panic::catch_unwind(|| zero_crossing_clicks(&commands)).is_err());
+        assert!(std::panic::catch_unwind(|| zeros_in_click_range(&commands, 0, u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_run_window() {
+        let test_puzzle_input = sample_commands();
+        let (head, tail) = test_puzzle_input.split_at(5);
+
+        assert_eq!(run_window(50, head, CountMode::Landings), (55, 1));
+        assert_eq!(run_window(50, head, CountMode::Crossings), (55, 3));
+
+        for (mode, total) in [(CountMode::Landings, 3), (CountMode::Crossings, 6)] {
+            let (middle, head_zeros) = run_window(50, head, mode);
+            let (end, tail_zeros) = run_window(middle, tail, mode);
+            assert_eq!(end, 32);
+            assert_eq!(head_zeros + tail_zeros, total);
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let test_puzzle_input = sample_commands();
+
+        assert_eq!(
+            run_until(50, &test_puzzle_input, |position| position > 90),
+            (3, 95)
+        );
+        assert_eq!(
+            run_until(50, &test_puzzle_input, |position| position > 100),
+            (10, 32)
+        );
+        assert_eq!(run_until(150, &[], |_| true), (0, 50));
+    }
+
+    #[test]
+    fn test_run_until_first_zero() {
+        let test_puzzle_input = sample_commands();
+
+        assert_eq!(
+            run_until_first_zero(50, &test_puzzle_input, CountMode::Landings),
+            (2, 0)
+        );
+        assert_eq!(
+            run_until_first_zero(50, &test_puzzle_input, CountMode::Crossings),
+            (0, 82)
+        );
+        assert_eq!(
+            run_until_first_zero(50, &test_puzzle_input[..2], CountMode::Landings),
+            (2, 52)
+        );
+    }
+
+    #[test]
+    fn test_simulate_small_dial() {
+        let commands = parse_commands("R7\nL13\nR25\n").unwrap();
+
+        // 5 -> 2 passes 0 once, 2 -> 9 twice and 9 -> 4 three times.
+        assert_eq!(simulate(10, 5, &commands, CountMode::Crossings), (4, 6));
+        assert_eq!(simulate(10, 5, &commands, CountMode::Landings), (4, 0));
+        assert_eq!(
+            simulate(10, 5, &commands[2..], CountMode::Crossings),
+            (0, 3)
+        );
+
+        for start in [15, -5] {
+            assert_eq!(simulate(10, start, &commands, CountMode::Crossings), (4, 6));
+        }
+        assert_eq!(simulate(10, 15, &[], CountMode::Landings), (5, 0));
+
+        let l30 = [RotationCommand::parse("L30").unwrap()];
+        assert_eq!(simulate(10, 0, &l30, CountMode::Crossings), (0, 3));
+        assert_eq!(simulate(10, 0, &l30, CountMode::Landings), (0, 1));
+
+        assert_eq!(
+            simulate(
+                DEFAULT_SIZE,
+                DEFAULT_START,
+                &sample_commands(),
+                CountMode::Crossings
+            ),
+            (32, 6)
+        );
+    }
+
+    #[test]
+    fn test_extra_crossings() {
+        assert_eq!(extra_crossings(&sample_commands()).unwrap(), 3);
+        assert_eq!(extra_crossings(&[]).unwrap(), 0);
+
+        let resting = parse_commands("R50\nR0\nR0\n").unwrap();
+        assert_eq!(extra_crossings(&resting).unwrap(), 0);
+        assert!(extra_crossings(&[RotationCommand::parse("R-5").unwrap()]).is_err());
+    }
+
+    #[test]
+    fn test_zero_crossing_clicks() {
+        let test_puzzle_input = sample_commands();
+        let zero_clicks = zero_crossing_clicks(&test_puzzle_input);
+
+        assert_eq!(zero_clicks.len(), 6);
+        assert_eq!(zero_clicks[0], 50);
+        assert_eq!(zero_clicks[1], 68 + 30 + 48);
+    }
+
+    #[test]
+    fn test_zeros_in_click_range() {
+        let test_puzzle_input = sample_commands();
+
+        let zero_clicks = zero_crossing_clicks(&test_puzzle_input);
+
+        for &click in &zero_clicks {
+            assert_eq!(
+                zeros_in_click_range(&test_puzzle_input, click, click + 1),
+                1
+            );
+            assert_eq!(
+                zeros_in_click_range(&test_puzzle_input, click - 1, click),
+                0
+            );
+        }
+        assert_eq!(zeros_in_click_range(&test_puzzle_input, 40, 60), 1);
+        assert_eq!(zeros_in_click_range(&test_puzzle_input, 51, 146), 0);
+        assert_eq!(zeros_in_click_range(&test_puzzle_input, 51, 147), 1);
+        assert_eq!(zeros_in_click_range(&test_puzzle_input, 0, u64::MAX), 6);
+        assert_eq!(zeros_in_click_range(&test_puzzle_input, 60, 40), 0);
+
+        let with_jump = parse_commands("L50\nA0\nR250\n").unwrap();
+        let zero_clicks = zero_crossing_clicks(&with_jump);
+        assert_eq!(zero_clicks, [50, 51, 151, 251]);
+        assert_eq!(zeros_in_click_range(&with_jump, 51, 52), 1);
+        assert_eq!(zeros_in_click_range(&with_jump, 52, 251), 1);
+        assert_eq!(zeros_in_click_range(&with_jump, 0, u64::MAX), 4);
+    }
+
+    #[test]
+    fn test_reverse_sequence() {
+        let test_puzzle_input = sample_commands();
+        let reversed = reverse_sequence(&test_puzzle_input);
+
+        assert_eq!(reversed.first().unwrap().to_string(), "R82");
+        assert_eq!(reversed.last().unwrap().to_string(), "R68");
+
+        let (middle, _) = run_window(50, &test_puzzle_input, CountMode::Landings);
+        assert_eq!(run_window(middle, &reversed, CountMode::Landings).0, 50);
+
+        let with_jump = parse_commands("L68\nA37\nR10\n").unwrap();
+        let reversed = reverse_sequence(&with_jump);
+        assert_eq!(reversed, parse_commands("L10\nA82\nR68\n").unwrap());
+        let (middle, _) = run_window(50, &with_jump, CountMode::Landings);
+        assert_eq!(run_window(middle, &reversed, CountMode::Landings).0, 50);
+    }
+
+    #[test]
+    fn test_detect_cycle() {
+        let repetitive = parse_commands("L25\nR50\nR50\nR50\n").unwrap();
+        assert_eq!(detect_cycle(&repetitive), Some((1, 2)));
+
+        let test_puzzle_input = sample_commands();
+        assert_eq!(detect_cycle(&test_puzzle_input), Some((3, 3)));
+        assert_eq!(detect_cycle(&test_puzzle_input[..3]), None);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::command::{Direction, RotationCommand};
+use anyhow::{Context, bail};
+use std::io::{ErrorKind, Read, Write};
+
+/// Writes `commands` as one direction byte (`L`, `R` or `A`) followed by the zigzag-encoded
+/// distance as a LEB128 varint, so typical commands take two or three bytes.
+pub fn write_commands_binary<W: Write>(
+    commands: &[RotationCommand],
+    w: &mut W,
+) -> anyhow::Result<()> {
+    let mut buffer = Vec::with_capacity(commands.len() * 3);
+    for command in commands {
+        buffer.push(direction_byte(&command.direction));
+        let mut value = zigzag(command.distance);
+        while value >= 0x80 {
+            buffer.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        buffer.push(value as u8);
+    }
+    w.write_all(&buffer)
+        .context("failed to write binary commands")
+}
+
+/// Reads commands written by [`write_commands_binary`] until the end of `r`.
+pub fn read_commands_binary<R: Read>(r: &mut R) -> anyhow::Result<Vec<RotationCommand>> {
+    let mut commands = Vec::new();
+    while let Some(byte) = next_byte(r)? {
+        let direction = Direction::try_from(char::from(byte))
+            .with_context(|| format!("invalid direction byte {byte:#04x}"))?;
+        let mut value: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = next_byte(r)?.context("binary commands end inside a distance")?;
+            if shift == 28 && byte > 0x0f {
+                bail!("distance varint overflows 32 bits");
+            }
+            value |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        commands.push(RotationCommand {
+            direction,
+            distance: unzigzag(value),
+        });
+    }
+    Ok(commands)
+}
+
+fn direction_byte(direction: &Direction) -> u8 {
+    match direction {
+        Direction::Left => b'L',
+        Direction::Right => b'R',
+        Direction::Absolute => b'A',
+    }
+}
+
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+fn unzigzag(value: u32) -> i32 {
+    (value >> 1) as i32 ^ -((value & 1) as i32)
+}
+
+fn next_byte<R: Read>(r: &mut R) -> anyhow::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    loop {
+        match r.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e).context("failed to read binary commands"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::sample_commands;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut commands = sample_commands();
+        commands.push(RotationCommand::parse("R-5").unwrap());
+        commands.push(RotationCommand::parse("A2147483647").unwrap());
+
+        let mut encoded = Vec::new();
+        write_commands_binary(&commands, &mut encoded).unwrap();
+        assert_eq!(&encoded[..2], [b'L', 136]);
+
+        let decoded = read_commands_binary(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, commands);
+    }
+
+    #[test]
+    fn test_binary_rejects_truncated_input() {
+        assert!(read_commands_binary(&mut [b'R'].as_slice()).is_err());
+        assert!(read_commands_binary(&mut [b'R', 0x80].as_slice()).is_err());
+        assert!(read_commands_binary(&mut [b'X', 1].as_slice()).is_err());
+        assert!(read_commands_binary(&mut [].as_slice()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,1050 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::num::{IntErrorKind, ParseIntError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DirectionParseError {
+    #[error("unsupported direction '{0}'")]
+    Unsupported(char),
+
+    #[error("unsupported direction code {0}")]
+    UnsupportedCode(u32),
+}
+
+#[derive(Debug, Error)]
+pub enum RotationCommandParseError {
+    #[error("empty input")]
+    EmptyInput,
+
+    #[error("invalid direction '{dir}' in '{input}'")]
+    InvalidDirection {
+        input: String,
+        dir: char,
+        column: usize,
+        #[source]
+        source: DirectionParseError,
+    },
+
+    #[error("missing distance in '{input}'")]
+    MissingDistance { input: String, column: usize },
+
+    #[error("invalid distance '{distance}' in '{input}'")]
+    InvalidDistance {
+        input: String,
+        distance: String,
+        column: usize,
+        #[source]
+        source: ParseIntError,
+    },
+
+    #[error(
+        "distance '{distance}' in '{input}' does not fit in an i32; a wider integer type is needed"
+    )]
+    DistanceOverflow {
+        input: String,
+        distance: String,
+        column: usize,
+    },
+
+    #[error("misplaced '_' separator in distance '{distance}' in '{input}'")]
+    MisplacedUnderscore {
+        input: String,
+        distance: String,
+        column: usize,
+    },
+
+    #[error("misplaced ',' separator in distance '{distance}' in '{input}'")]
+    MisplacedComma {
+        input: String,
+        distance: String,
+        column: usize,
+    },
+
+    #[error("invalid revolutions '{distance}' in '{input}'")]
+    InvalidRevolutions {
+        input: String,
+        distance: String,
+        column: usize,
+    },
+
+    #[error("repeat count '{count}' in '{input}' is not between 1 and {MAX_REPEAT}")]
+    InvalidRepeat {
+        input: String,
+        count: String,
+        column: usize,
+    },
+
+    #[error("zero distance in '{input}' does not move the dial")]
+    ZeroDistance { input: String, column: usize },
+
+    #[error("non-ASCII byte at position {position}")]
+    NonAscii { position: usize },
+}
+
+impl RotationCommandParseError {
+    /// Byte offset where the problem starts, if it has one. It counts into the line returned by
+    /// [`input`](Self::input), or into the raw bytes for [`NonAscii`](Self::NonAscii).
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            RotationCommandParseError::EmptyInput => None,
+            RotationCommandParseError::InvalidDirection { column, .. }
+            | RotationCommandParseError::MissingDistance { column, .. }
+            | RotationCommandParseError::InvalidDistance { column, .. }
+            | RotationCommandParseError::DistanceOverflow { column, .. }
+            | RotationCommandParseError::MisplacedUnderscore { column, .. }
+            | RotationCommandParseError::MisplacedComma { column, .. }
+            | RotationCommandParseError::InvalidRevolutions { column, .. }
+            | RotationCommandParseError::InvalidRepeat { column, .. }
+            | RotationCommandParseError::ZeroDistance { column, .. } => Some(*column),
+            RotationCommandParseError::NonAscii { position } => Some(*position),
+        }
+    }
+
+    /// The trimmed line that failed to parse, if the error kept it.
+    pub fn input(&self) -> Option<&str> {
+        match self {
+            RotationCommandParseError::EmptyInput | RotationCommandParseError::NonAscii { .. } => {
+                None
+            }
+            RotationCommandParseError::InvalidDirection { input, .. }
+            | RotationCommandParseError::MissingDistance { input, .. }
+            | RotationCommandParseError::InvalidDistance { input, .. }
+            | RotationCommandParseError::DistanceOverflow { input, .. }
+            | RotationCommandParseError::MisplacedUnderscore { input, .. }
+            | RotationCommandParseError::MisplacedComma { input, .. }
+            | RotationCommandParseError::InvalidRevolutions { input, .. }
+            | RotationCommandParseError::InvalidRepeat { input, .. }
+            | RotationCommandParseError::ZeroDistance { input, .. } => Some(input),
+        }
+    }
+}
+
+/// Renders `line` with a caret under the column of `err` on the line below, e.g.
+///
+/// ```text
+/// R1a2
+///   ^
+/// ```
+///
+/// `line` must be the [`input`](RotationCommandParseError::input) of `err`, or the same line
+/// trimmed, since the column counts bytes into it.
+pub fn render_diagnostic(line: &str, err: &RotationCommandParseError) -> String {
+    let column = err.column().unwrap_or_default();
+    let indent = line
+        .get(..column)
+        .map_or(column, |prefix| prefix.chars().count());
+    format!("{line}\n{:>width$}", "^", width = indent + 1)
+}
+
+/// Largest count [`RotationCommand::parse_repeat`] accepts in an `x<count>` suffix.
+pub const MAX_REPEAT: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Left,
+    Right,
+    /// Moves straight to the position given as the distance, as in `A37`.
+    Absolute,
+}
+
+impl Direction {
+    fn get_direction_literal(&self) -> &'static str {
+        match self {
+            Direction::Left => "L",
+            Direction::Right => "R",
+            Direction::Absolute => "A",
+        }
+    }
+
+    /// The direction that turns the dial back, or `None` for an absolute jump, which can only
+    /// be reverted by jumping back to the position it left.
+    pub fn opposite(&self) -> Option<Direction> {
+        match self {
+            Direction::Left => Some(Direction::Right),
+            Direction::Right => Some(Direction::Left),
+            Direction::Absolute => None,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'R' | '→' => Ok(Direction::Right),
+            'L' | '←' => Ok(Direction::Left),
+            'A' => Ok(Direction::Absolute),
+            other => Err(DirectionParseError::Unsupported(other)),
+        }
+    }
+}
+
+/// Decodes the numeric form used by compact encodings: 0 is left and 1 is right.
+impl TryFrom<u32> for Direction {
+    type Error = DirectionParseError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Direction::Left),
+            1 => Ok(Direction::Right),
+            other => Err(DirectionParseError::UnsupportedCode(other)),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_direction_literal())
+    }
+}
+
+/// Switches for [`RotationCommand::parse_with`]. The default is the lenient mode used by
+/// [`RotationCommand::parse`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Rejects no-op commands such as `R0` with [`RotationCommandParseError::ZeroDistance`].
+    pub strict: bool,
+    /// Drops `,` thousands separators from the distance, so `R1,000` is `R1000`. The commas
+    /// must group the digits in threes, failing with
+    /// [`RotationCommandParseError::MisplacedComma`] otherwise. A line can
+    /// then no longer hold several comma-separated commands such as `R1,L2`: the comma is
+    /// read as part of the distance and the line fails to parse.
+    pub thousands_commas: bool,
+    /// Skips a leading line number such as the `12:` in `12: R48`, as left behind when
+    /// pasting from an editor.
+    pub line_numbers: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationCommand {
+    pub direction: Direction,
+    pub distance: i32,
+}
+
+impl RotationCommand {
+    /// Parses a line such as `R8` or `L68`.
+    ///
+    /// The distance may carry a single explicit `+` sign, so `R+8` is the same as `R8`, and
+    /// may group digits with single `_` separators like a Rust literal (`R1_000`). Tabs between
+    /// the direction and the distance are skipped, so TSV rows like `R\t8` parse too.
+    pub fn parse(input: &str) -> anyhow::Result<Self, RotationCommandParseError> {
+        Self::parse_with(input, ParseOptions::default())
+    }
+
+    pub fn parse_with(
+        input: &str,
+        options: ParseOptions,
+    ) -> anyhow::Result<Self, RotationCommandParseError> {
+        let line = input.trim();
+        let (offset, input) = if options.line_numbers {
+            let command = strip_line_number(line);
+            (line.len() - command.len(), command)
+        } else {
+            (0, line)
+        };
+        if input.is_empty() {
+            return Err(RotationCommandParseError::EmptyInput);
+        }
+        let mut chars = input.chars();
+        let dir_ch = chars.next().ok_or(RotationCommandParseError::EmptyInput)?;
+
+        let direction = Direction::try_from(dir_ch).map_err(|e| {
+            RotationCommandParseError::InvalidDirection {
+                input: line.to_string(),
+                dir: dir_ch,
+                column: offset,
+                source: e,
+            }
+        })?;
+
+        let after_direction = chars.as_str();
+        let distance_str = after_direction.trim_start_matches('\t');
+        let distance_column =
+            offset + dir_ch.len_utf8() + (after_direction.len() - distance_str.len());
+        if distance_str.is_empty() {
+            return Err(RotationCommandParseError::MissingDistance {
+                input: line.to_string(),
+                column: distance_column,
+            });
+        }
+
+        let ungrouped = if options.thousands_commas {
+            strip_thousands_commas(line, distance_str, distance_column)?
+        } else {
+            Cow::Borrowed(distance_str)
+        };
+        let distance: i32 = strip_digit_separators(line, &ungrouped, distance_column)?
+            .parse()
+            .map_err(|e| {
+                distance_error(
+                    line,
+                    distance_str,
+                    distance_column,
+                    options.thousands_commas,
+                    e,
+                )
+            })?;
+
+        if options.strict && distance == 0 {
+            return Err(RotationCommandParseError::ZeroDistance {
+                input: line.to_string(),
+                column: distance_column,
+            });
+        }
+
+        Ok(Self {
+            direction,
+            distance,
+        })
+    }
+
+    /// Parses a line whose distance counts revolutions of a dial with `size` positions, so
+    /// `R2.5` on a 100-position dial is `R250`.
+    ///
+    /// The distance is `whole[.fraction]` with digits on both sides of the point and at most
+    /// nine fractional digits. It becomes `size * whole + round(fraction * size)` clicks, where
+    /// an exact half click rounds up (`R0.005` on size 100 is `R1`). Signs, separators, extra
+    /// points and totals beyond `i32::MAX` are rejected with
+    /// [`RotationCommandParseError::InvalidRevolutions`].
+    #[cfg(feature = "revolutions")]
+    pub fn parse_revolutions(
+        input: &str,
+        size: u32,
+    ) -> anyhow::Result<Self, RotationCommandParseError> {
+        let input = input.trim();
+        let mut chars = input.chars();
+        let dir_ch = chars.next().ok_or(RotationCommandParseError::EmptyInput)?;
+        let direction = Direction::try_from(dir_ch).map_err(|e| {
+            RotationCommandParseError::InvalidDirection {
+                input: input.to_string(),
+                dir: dir_ch,
+                column: 0,
+                source: e,
+            }
+        })?;
+
+        let distance_str = chars.as_str();
+        let column = dir_ch.len_utf8();
+        if distance_str.is_empty() {
+            return Err(RotationCommandParseError::MissingDistance {
+                input: input.to_string(),
+                column,
+            });
+        }
+
+        let clicks = revolutions_to_clicks(distance_str, size).ok_or_else(|| {
+            RotationCommandParseError::InvalidRevolutions {
+                input: input.to_string(),
+                distance: distance_str.to_string(),
+                column,
+            }
+        })?;
+
+        Ok(Self {
+            direction,
+            distance: clicks,
+        })
+    }
+
+    /// Parses a line with an optional `x<count>` suffix, so `R8x3` is `R8` three times. The
+    /// count must be between 1 and [`MAX_REPEAT`]; a line without a suffix yields one command.
+    pub fn parse_repeat(input: &str) -> anyhow::Result<Vec<Self>, RotationCommandParseError> {
+        let input = input.trim();
+        let Some((command, count)) = input.rsplit_once('x') else {
+            return Ok(vec![Self::parse(input)?]);
+        };
+
+        let repeats = count
+            .parse::<u32>()
+            .ok()
+            .filter(|repeats| (1..=MAX_REPEAT).contains(repeats))
+            .ok_or_else(|| RotationCommandParseError::InvalidRepeat {
+                input: input.to_string(),
+                count: count.to_string(),
+                column: command.len() + 1,
+            })?;
+        let command = Self::parse(command)?;
+        Ok(vec![command; repeats as usize])
+    }
+
+    /// Parses the distance-first form such as `8R` or `68L`, where the direction is the last
+    /// character and everything before it is the distance.
+    pub fn parse_suffix(input: &str) -> anyhow::Result<Self, RotationCommandParseError> {
+        let input = input.trim();
+        let dir_ch = input
+            .chars()
+            .next_back()
+            .ok_or(RotationCommandParseError::EmptyInput)?;
+        let distance_str = &input[..input.len() - dir_ch.len_utf8()];
+
+        let direction = Direction::try_from(dir_ch).map_err(|e| {
+            RotationCommandParseError::InvalidDirection {
+                input: input.to_string(),
+                dir: dir_ch,
+                column: distance_str.len(),
+                source: e,
+            }
+        })?;
+        if distance_str.is_empty() {
+            return Err(RotationCommandParseError::MissingDistance {
+                input: input.to_string(),
+                column: 0,
+            });
+        }
+
+        let distance = distance_str
+            .parse()
+            .map_err(|e| distance_error(input, distance_str, 0, false, e))?;
+
+        Ok(Self {
+            direction,
+            distance,
+        })
+    }
+
+    /// The number of clicks a left or right turn covers, or `None` for an absolute jump, whose
+    /// `distance` is the target position instead.
+    pub fn turn_distance(&self) -> Option<i32> {
+        match self.direction {
+            Direction::Left | Direction::Right => Some(self.distance),
+            Direction::Absolute => None,
+        }
+    }
+
+    /// The same command with its distance taken modulo `size`, which ends on the same position
+    /// of a dial with `size` positions. Only valid for solution one: the full turns it drops
+    /// are exactly the ones solution two counts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn reduced(&self, size: u32) -> RotationCommand {
+        RotationCommand {
+            direction: self.direction,
+            distance: (i64::from(self.distance) % i64::from(size)) as i32,
+        }
+    }
+
+    /// Parses a command straight from raw bytes.
+    ///
+    /// Plain `L<digits>` / `R<digits>` lines are decoded without building a `str`; anything
+    /// else falls back to [`RotationCommand::parse`] so errors stay identical.
+    pub fn parse_bytes(input: &[u8]) -> anyhow::Result<Self, RotationCommandParseError> {
+        if let Some(position) = input.iter().position(|byte| !byte.is_ascii()) {
+            return Err(RotationCommandParseError::NonAscii { position });
+        }
+
+        if let [dir_byte @ (b'L' | b'R'), digits @ ..] = input
+            && !digits.is_empty()
+            && let Some(distance) = digits.iter().try_fold(0i32, |acc, byte| {
+                if !byte.is_ascii_digit() {
+                    return None;
+                }
+                acc.checked_mul(10)?.checked_add(i32::from(byte - b'0'))
+            })
+        {
+            let direction = match dir_byte {
+                b'L' => Direction::Left,
+                _ => Direction::Right,
+            };
+            return Ok(Self {
+                direction,
+                distance,
+            });
+        }
+
+        Self::parse(&String::from_utf8_lossy(input))
+    }
+}
+
+/// Converts `whole[.fraction]` revolutions of a `size`-position dial to clicks, rounding an
+/// exact half click up. `None` when the text is malformed or the total overflows `i32`.
+#[cfg(feature = "revolutions")]
+fn revolutions_to_clicks(revolutions: &str, size: u32) -> Option<i32> {
+    const MAX_FRACTION_DIGITS: usize = 9;
+
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|byte| byte.is_ascii_digit());
+    let (whole, fraction) = revolutions.split_once('.').unwrap_or((revolutions, "0"));
+    if !is_digits(whole) || !is_digits(fraction) || fraction.len() > MAX_FRACTION_DIGITS {
+        return None;
+    }
+
+    let size = u128::from(size);
+    let scale = 10u128.pow(fraction.len() as u32);
+    let partial = (2 * fraction.parse::<u128>().ok()? * size + scale) / (2 * scale);
+    let clicks = whole
+        .parse::<u128>()
+        .ok()?
+        .checked_mul(size)?
+        .checked_add(partial)?;
+    i32::try_from(clicks).ok()
+}
+
+/// Drops a leading `<digits>:` line number and the whitespace after it, if there is one.
+fn strip_line_number(input: &str) -> &str {
+    let digits = input.len()
+        - input
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    match input[digits..].strip_prefix(':') {
+        Some(command) if digits > 0 => command.trim_start(),
+        _ => input,
+    }
+}
+
+/// Removes `_` digit separators, rejecting leading, trailing and doubled underscores.
+fn strip_digit_separators<'a>(
+    input: &str,
+    distance: &'a str,
+    column: usize,
+) -> Result<Cow<'a, str>, RotationCommandParseError> {
+    if !distance.contains('_') {
+        return Ok(Cow::Borrowed(distance));
+    }
+
+    let digits = distance.strip_prefix('+').unwrap_or(distance);
+    if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+        return Err(RotationCommandParseError::MisplacedUnderscore {
+            input: input.to_string(),
+            distance: distance.to_string(),
+            column,
+        });
+    }
+
+    Ok(Cow::Owned(distance.replace('_', "")))
+}
+
+/// Drops the `,` separators from `distance`, which must group its digits in threes after a
+/// leading group of one to three digits, as in `12,345`.
+fn strip_thousands_commas<'a>(
+    input: &str,
+    distance: &'a str,
+    column: usize,
+) -> Result<Cow<'a, str>, RotationCommandParseError> {
+    if !distance.contains(',') {
+        return Ok(Cow::Borrowed(distance));
+    }
+
+    let digits = distance.trim_start_matches(['+', '-']);
+    let mut groups = digits.split(',');
+    let leading = groups.next().unwrap_or_default();
+    if !(1..=3).contains(&leading.len()) || groups.any(|group| group.len() != 3) {
+        return Err(RotationCommandParseError::MisplacedComma {
+            input: input.to_string(),
+            distance: distance.to_string(),
+            column,
+        });
+    }
+
+    Ok(Cow::Owned(distance.replace(',', "")))
+}
+
+/// Turns a failed parse of the distance starting at `column` into an error, reporting values
+/// beyond the range of an `i32` as [`RotationCommandParseError::DistanceOverflow`]. `commas`
+/// says whether `distance` may still hold thousands separators.
+fn distance_error(
+    input: &str,
+    distance: &str,
+    column: usize,
+    commas: bool,
+    source: ParseIntError,
+) -> RotationCommandParseError {
+    match source.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            RotationCommandParseError::DistanceOverflow {
+                input: input.to_string(),
+                distance: distance.to_string(),
+                column,
+            }
+        }
+        _ => RotationCommandParseError::InvalidDistance {
+            input: input.to_string(),
+            distance: distance.to_string(),
+            column: column + first_invalid_digit(distance, commas),
+            source,
+        },
+    }
+}
+
+/// Byte offset of the first character that cannot belong to a distance, or 0 when every
+/// character could (for example when the value merely overflows). With `commas` a `,`
+/// thousands separator counts as part of the distance.
+fn first_invalid_digit(distance: &str, commas: bool) -> usize {
+    distance
+        .char_indices()
+        .find(|&(index, ch)| {
+            !(ch.is_ascii_digit()
+                || ch == '_'
+                || (commas && ch == ',')
+                || (index == 0 && matches!(ch, '+' | '-')))
+        })
+        .map_or(0, |(index, _)| index)
+}
+
+impl Display for RotationCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.direction, self.distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_command_right_direction() {
+        let r = RotationCommand::parse("R12").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 12);
+    }
+
+    #[test]
+    fn test_rotation_command_left_direction() {
+        let r = RotationCommand::parse("L21").unwrap();
+        assert_eq!(r.direction, Direction::Left);
+        assert_eq!(r.distance, 21);
+    }
+
+    #[test]
+    fn test_rotation_command_arrow_directions() {
+        let r = RotationCommand::parse("→8").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 8);
+
+        let l = RotationCommand::parse("←99").unwrap();
+        assert_eq!(l.direction, Direction::Left);
+        assert_eq!(l.distance, 99);
+
+        assert_eq!(RotationCommand::parse("→1a").unwrap_err().column(), Some(4));
+    }
+
+    #[test]
+    fn test_rotation_command_tab_separated() {
+        let r = RotationCommand::parse("R\t8").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 8);
+
+        assert_eq!(RotationCommand::parse("L\t\t21").unwrap().distance, 21);
+        assert!(matches!(
+            RotationCommand::parse("R\t").unwrap_err(),
+            RotationCommandParseError::MissingDistance { .. }
+        ));
+        assert_eq!(
+            RotationCommand::parse("R\t8x").unwrap_err().column(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_rotation_command_reduced() {
+        let reduced = RotationCommand::parse("R1003").unwrap().reduced(100);
+        assert_eq!(reduced.direction, Direction::Right);
+        assert_eq!(reduced.distance, 3);
+
+        assert_eq!(
+            RotationCommand::parse("L200")
+                .unwrap()
+                .reduced(100)
+                .to_string(),
+            "L0"
+        );
+        assert_eq!(
+            RotationCommand::parse("L68")
+                .unwrap()
+                .reduced(100)
+                .to_string(),
+            "L68"
+        );
+    }
+
+    #[test]
+    fn test_rotation_command_equality() {
+        assert_eq!(
+            RotationCommand::parse("R8").unwrap(),
+            RotationCommand::parse("R8").unwrap()
+        );
+        assert_eq!(
+            RotationCommand::parse("R+8").unwrap(),
+            RotationCommand::parse_suffix("8R").unwrap()
+        );
+        assert_ne!(
+            RotationCommand::parse("R8").unwrap(),
+            RotationCommand::parse("L8").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_opposite_and_turn_distance() {
+        assert_eq!(Direction::Left.opposite(), Some(Direction::Right));
+        assert_eq!(Direction::Right.opposite(), Some(Direction::Left));
+        assert_eq!(Direction::Absolute.opposite(), None);
+
+        assert_eq!(
+            RotationCommand::parse("L68").unwrap().turn_distance(),
+            Some(68)
+        );
+        assert_eq!(RotationCommand::parse("A99").unwrap().turn_distance(), None);
+    }
+
+    #[test]
+    fn test_rotation_command_absolute() {
+        let a = RotationCommand::parse("A37").unwrap();
+        assert_eq!(a.direction, Direction::Absolute);
+        assert_eq!(a.distance, 37);
+        assert_eq!(a.to_string(), "A37");
+    }
+
+    #[test]
+    fn test_direction_from_numeric_code() {
+        assert_eq!(Direction::try_from(0u32), Ok(Direction::Left));
+        assert_eq!(Direction::try_from(1u32), Ok(Direction::Right));
+        assert_eq!(
+            Direction::try_from(2u32),
+            Err(DirectionParseError::UnsupportedCode(2))
+        );
+    }
+
+    #[test]
+    fn test_direction_sorting_and_hashing() {
+        let mut directions = vec![Direction::Right, Direction::Left, Direction::Right];
+        directions.sort();
+        assert_eq!(
+            directions,
+            [Direction::Left, Direction::Right, Direction::Right]
+        );
+
+        let mut counts = std::collections::HashMap::new();
+        for direction in directions {
+            *counts.entry(direction).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&Direction::Left], 1);
+        assert_eq!(counts[&Direction::Right], 2);
+    }
+
+    #[test]
+    fn test_invalid_rotation_commands() {
+        assert!(matches!(
+            RotationCommand::parse("").unwrap_err(),
+            RotationCommandParseError::EmptyInput
+        ));
+        assert!(matches!(
+            RotationCommand::parse("X99").unwrap_err(),
+            RotationCommandParseError::InvalidDirection { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse("R").unwrap_err(),
+            RotationCommandParseError::MissingDistance { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse("Rabc").unwrap_err(),
+            RotationCommandParseError::InvalidDistance { .. }
+        ));
+    }
+
+    #[test]
+    fn test_distance_overflow() {
+        let error = RotationCommand::parse("R99999999999").unwrap_err();
+        assert!(matches!(
+            &error,
+            RotationCommandParseError::DistanceOverflow { distance, column: 1, .. }
+                if distance == "99999999999"
+        ));
+        assert!(error.to_string().contains("does not fit in an i32"));
+
+        assert!(matches!(
+            RotationCommand::parse("L-2147483649").unwrap_err(),
+            RotationCommandParseError::DistanceOverflow { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_suffix("99999999999R").unwrap_err(),
+            RotationCommandParseError::DistanceOverflow { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"R99999999999").unwrap_err(),
+            RotationCommandParseError::DistanceOverflow { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_columns() {
+        assert_eq!(RotationCommand::parse("X9").unwrap_err().column(), Some(0));
+        assert_eq!(
+            RotationCommand::parse("R1a2").unwrap_err().column(),
+            Some(2)
+        );
+        assert_eq!(
+            RotationCommand::parse("  R1a2").unwrap_err().column(),
+            Some(2)
+        );
+        assert_eq!(RotationCommand::parse("R").unwrap_err().column(), Some(1));
+        assert_eq!(
+            RotationCommand::parse("R++8").unwrap_err().column(),
+            Some(2)
+        );
+        assert_eq!(
+            RotationCommand::parse("R-5a").unwrap_err().column(),
+            Some(3)
+        );
+        assert_eq!(
+            RotationCommand::parse("R--5").unwrap_err().column(),
+            Some(2)
+        );
+        assert_eq!(RotationCommand::parse("").unwrap_err().column(), None);
+    }
+
+    #[test]
+    fn test_render_diagnostic() {
+        let error = RotationCommand::parse("R1a2").unwrap_err();
+        assert_eq!(render_diagnostic("R1a2", &error), "R1a2\n  ^");
+        assert_eq!(error.input(), Some("R1a2"));
+
+        let error = RotationCommand::parse("→1a").unwrap_err();
+        assert_eq!(render_diagnostic("→1a", &error), "→1a\n  ^");
+
+        let error = RotationCommand::parse("  R4x").unwrap_err();
+        assert_eq!(
+            render_diagnostic(error.input().unwrap(), &error),
+            "R4x\n  ^"
+        );
+
+        let line_numbers = ParseOptions {
+            line_numbers: true,
+            ..ParseOptions::default()
+        };
+        let error = RotationCommand::parse_with("12: R4x", line_numbers).unwrap_err();
+        assert_eq!(
+            render_diagnostic(error.input().unwrap(), &error),
+            "12: R4x\n      ^"
+        );
+    }
+
+    #[test]
+    fn test_rotation_command_explicit_plus_sign() {
+        let r = RotationCommand::parse("R+8").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 8);
+
+        let r = RotationCommand::parse_bytes(b"R+8").unwrap();
+        assert_eq!(r.distance, 8);
+
+        assert!(matches!(
+            RotationCommand::parse("R++8").unwrap_err(),
+            RotationCommandParseError::InvalidDistance { .. }
+        ));
+    }
+
+    #[test]
+    fn test_rotation_command_underscore_separators() {
+        let r = RotationCommand::parse("R1_000").unwrap();
+        assert_eq!(r.distance, 1000);
+
+        for input in ["R1__0", "R_10", "R10_"] {
+            assert!(matches!(
+                RotationCommand::parse(input).unwrap_err(),
+                RotationCommandParseError::MisplacedUnderscore { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_zero_distance_strict_and_lenient() {
+        let r = RotationCommand::parse("R0").unwrap();
+        assert_eq!(r.distance, 0);
+
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            RotationCommand::parse_with("R0", strict).unwrap_err(),
+            RotationCommandParseError::ZeroDistance { .. }
+        ));
+        assert_eq!(
+            RotationCommand::parse_with("L5", strict).unwrap().distance,
+            5
+        );
+    }
+
+    #[test]
+    fn test_thousands_commas() {
+        let commas = ParseOptions {
+            thousands_commas: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            RotationCommand::parse_with("R1,000", commas)
+                .unwrap()
+                .distance,
+            1000
+        );
+        assert_eq!(
+            RotationCommand::parse_with("L12,345,678", commas)
+                .unwrap()
+                .distance,
+            12_345_678
+        );
+        assert!(RotationCommand::parse("R1,000").is_err());
+        assert!(RotationCommand::parse_with("R1,L2", commas).is_err());
+
+        let error = RotationCommand::parse_with("R1,00a", commas).unwrap_err();
+        assert_eq!(error.column(), Some(5));
+        assert_eq!(
+            render_diagnostic(error.input().unwrap(), &error),
+            "R1,00a\n     ^"
+        );
+
+        for input in ["R,5", "R5,", "R1,00", "R1234,567", "R1,,000"] {
+            assert!(matches!(
+                RotationCommand::parse_with(input, commas).unwrap_err(),
+                RotationCommandParseError::MisplacedComma { column: 1, .. }
+            ));
+        }
+    }
+
+    #[cfg(feature = "revolutions")]
+    #[test]
+    fn test_parse_revolutions() {
+        let r = RotationCommand::parse_revolutions("R2.5", 100).unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 250);
+
+        assert_eq!(
+            RotationCommand::parse_revolutions("L3", 100)
+                .unwrap()
+                .distance,
+            300
+        );
+        assert_eq!(
+            RotationCommand::parse_revolutions("R0.005", 100)
+                .unwrap()
+                .distance,
+            1
+        );
+        for input in ["R2.", "R.5", "R2.5.1", "R-2.5", "R2.x", "R99999999999.5"] {
+            assert!(matches!(
+                RotationCommand::parse_revolutions(input, 100).unwrap_err(),
+                RotationCommandParseError::InvalidRevolutions { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_repeat() {
+        let commands = RotationCommand::parse_repeat("R8x3").unwrap();
+        assert_eq!(commands.len(), 3);
+        assert!(
+            commands
+                .iter()
+                .all(|command| *command == RotationCommand::parse("R8").unwrap())
+        );
+
+        assert_eq!(RotationCommand::parse_repeat(" L5 ").unwrap().len(), 1);
+        for input in ["R8x0", "R8x", "R8x-1", "R8x10001"] {
+            assert!(matches!(
+                RotationCommand::parse_repeat(input).unwrap_err(),
+                RotationCommandParseError::InvalidRepeat { column: 3, .. }
+            ));
+        }
+        assert!(matches!(
+            RotationCommand::parse_repeat("R8ax2").unwrap_err(),
+            RotationCommandParseError::InvalidDistance { column: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_suffix() {
+        let r = RotationCommand::parse_suffix("8R").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 8);
+
+        let l = RotationCommand::parse_suffix(" 68L").unwrap();
+        assert_eq!(l.direction, Direction::Left);
+        assert_eq!(l.distance, 68);
+
+        assert!(matches!(
+            RotationCommand::parse_suffix("8X").unwrap_err(),
+            RotationCommandParseError::InvalidDirection { column: 1, .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_suffix("R").unwrap_err(),
+            RotationCommandParseError::MissingDistance { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_suffix("R8").unwrap_err(),
+            RotationCommandParseError::InvalidDirection { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_suffix("1a2R").unwrap_err(),
+            RotationCommandParseError::InvalidDistance { column: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_line_number_prefix() {
+        let line_numbers = ParseOptions {
+            line_numbers: true,
+            ..ParseOptions::default()
+        };
+        let r = RotationCommand::parse_with("12: R48", line_numbers).unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 48);
+
+        assert_eq!(
+            RotationCommand::parse_with("1:L68", line_numbers)
+                .unwrap()
+                .distance,
+            68
+        );
+        assert_eq!(
+            RotationCommand::parse_with("L68", line_numbers)
+                .unwrap()
+                .distance,
+            68
+        );
+        assert_eq!(
+            RotationCommand::parse_with("12: R4x", line_numbers)
+                .unwrap_err()
+                .column(),
+            Some(6)
+        );
+        assert!(RotationCommand::parse_with("12:", line_numbers).is_err());
+        assert!(RotationCommand::parse("12: R48").is_err());
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let r = RotationCommand::parse_bytes(b"R8").unwrap();
+        assert_eq!(r.direction, Direction::Right);
+        assert_eq!(r.distance, 8);
+
+        let l = RotationCommand::parse_bytes(b"L99").unwrap();
+        assert_eq!(l.direction, Direction::Left);
+        assert_eq!(l.distance, 99);
+    }
+
+    #[test]
+    fn test_parse_bytes_invalid_input() {
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"").unwrap_err(),
+            RotationCommandParseError::EmptyInput
+        ));
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"X9").unwrap_err(),
+            RotationCommandParseError::InvalidDirection { dir: 'X', .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"R").unwrap_err(),
+            RotationCommandParseError::MissingDistance { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"R1a").unwrap_err(),
+            RotationCommandParseError::InvalidDistance { .. }
+        ));
+        assert!(matches!(
+            RotationCommand::parse_bytes(b"R\xff8").unwrap_err(),
+            RotationCommandParseError::NonAscii { position: 1 }
+        ));
+    }
+}
@@ -0,0 +1,1620 @@
+use crate::command::{Direction, RotationCommand};
+use anyhow::{Context, bail};
+use num_traits::PrimInt;
+use std::fmt::{Display, Formatter};
+
+/// Integer types a [`SafeDialKnob`] can track its position with.
+pub trait DialInteger: PrimInt + Display {}
+
+impl<T: PrimInt + Display> DialInteger for T {}
+
+/// Number of positions on the puzzle's dial.
+pub const DEFAULT_SIZE: u32 = 100;
+
+/// Position the puzzle's dial points at before the first rotation.
+pub const DEFAULT_START: i32 = 50;
+
+/// A position on the puzzle's default dial, guaranteed to be in `0..DEFAULT_SIZE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position(u8);
+
+impl Position {
+    /// Returns `None` unless `position` is on the default dial.
+    pub fn new(position: i32) -> Option<Position> {
+        if (0..DEFAULT_SIZE as i32).contains(&position) {
+            u8::try_from(position).ok().map(Position)
+        } else {
+            None
+        }
+    }
+
+    /// Wraps any `position` onto the default dial.
+    pub(crate) fn wrapping(position: i32) -> Position {
+        Position(position.rem_euclid(DEFAULT_SIZE as i32) as u8)
+    }
+}
+
+impl From<Position> for i32 {
+    fn from(position: Position) -> Self {
+        i32::from(position.0)
+    }
+}
+
+/// Which zero events a run counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountMode {
+    /// Solution one: only the resting position after each rotation.
+    Landings,
+    /// Solution two: every click that points the dial at 0. A negative distance has no clicks
+    /// to step through, so the infallible methods panic on one in this mode;
+    /// [`SafeDialKnob::apply_rotation_commands_solution_two`] reports it as an error instead.
+    Crossings,
+}
+
+/// How solution two counts the click a rotation comes to rest on when it points at 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZeroCountPolicy {
+    /// Every click at 0 counts once, including the last one. This is the puzzle's rule.
+    #[default]
+    EndInclusive,
+    /// Only clicks at 0 before the last one count, so resting on 0 is ignored.
+    MidOnly,
+    /// Resting on 0 counts twice: once for passing through and once for landing.
+    Both,
+}
+
+/// Which way a right turn moves the dial.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Right turns towards higher numbers, as in the puzzle.
+    #[default]
+    Standard,
+    /// The mirror image: right turns towards lower numbers and left towards higher ones.
+    Inverted,
+}
+
+/// A safe's dial. Constructors taking a position wrap it onto the dial with
+/// `rem_euclid(size)`, so `-1` points at 99 on the default dial; only
+/// [`try_new`](SafeDialKnob::try_new) rejects such positions instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeDialKnob<T = i32> {
+    current_position: T,
+    zero_position_occurrence: u32,
+    start: T,
+    size: T,
+    target: T,
+    history: Option<Vec<T>>,
+    count_initial_zero: bool,
+    zero_count_policy: ZeroCountPolicy,
+    blocked: Vec<T>,
+    orientation: Orientation,
+}
+
+impl<T: DialInteger> Default for SafeDialKnob<T> {
+    fn default() -> Self {
+        SafeDialKnob {
+            current_position: dial_integer(i64::from(DEFAULT_START)),
+            zero_position_occurrence: 0,
+            start: dial_integer(i64::from(DEFAULT_START)),
+            size: dial_integer(i64::from(DEFAULT_SIZE)),
+            target: dial_integer(0),
+            history: None,
+            count_initial_zero: false,
+            zero_count_policy: ZeroCountPolicy::default(),
+            blocked: Vec::new(),
+            orientation: Orientation::default(),
+        }
+    }
+}
+
+impl SafeDialKnob {
+    pub fn init() -> Self {
+        SafeDialKnob::default()
+    }
+
+    /// A default dial that points at `position`, wrapped onto the dial.
+    pub(crate) fn at_position(position: i32) -> Self {
+        let position = i32::from(Position::wrapping(position));
+        SafeDialKnob {
+            current_position: position,
+            start: position,
+            ..SafeDialKnob::default()
+        }
+    }
+
+    /// Continues a run that stopped at `position` after counting `prior_count` zero events,
+    /// so chunked input adds up to the same total as a single pass.
+    pub fn resume_from(position: i32, prior_count: u32) -> Self {
+        SafeDialKnob {
+            zero_position_occurrence: prior_count,
+            ..SafeDialKnob::at_position(position)
+        }
+    }
+
+    /// The current position as a [`Position`] of the default dial. `None` only on a larger
+    /// dial from the builder that points past the default dial's last position.
+    pub fn position(&self) -> Option<Position> {
+        Position::new(self.current_position)
+    }
+
+    pub fn builder() -> SafeDialKnobBuilder {
+        SafeDialKnobBuilder::default()
+    }
+
+    /// A dial of `size` positions pointing at `start` and counting `target`. Unlike the
+    /// builder, positions outside `0..size` are rejected instead of wrapped.
+    pub fn try_new(size: u32, start: i32, target: i32) -> anyhow::Result<Self> {
+        if size == 0 {
+            bail!("dial size must be positive");
+        }
+        let positions = 0..i64::from(size);
+        if !positions.contains(&i64::from(start)) {
+            bail!("start position {start} is outside the dial 0..{size}");
+        }
+        if !positions.contains(&i64::from(target)) {
+            bail!("target position {target} is outside the dial 0..{size}");
+        }
+        SafeDialKnob::builder()
+            .size(size)
+            .start(start)
+            .target(target)
+            .build()
+    }
+
+    /// A default dial whose left and right turns follow `orientation`.
+    pub fn with_orientation(orientation: Orientation) -> Self {
+        SafeDialKnob {
+            orientation,
+            ..SafeDialKnob::default()
+        }
+    }
+
+    /// A default dial that can never point at the `blocked` positions: a click onto one
+    /// carries straight on to the next free position in the same direction without using
+    /// up a step. Blocked positions still take their place in the `0..size` modulus, and
+    /// each is wrapped onto the dial like any other position. When the default start is
+    /// blocked the dial starts at the next free position to the right instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every position of the dial is blocked.
+    pub fn with_blocked(blocked: &[i32]) -> Self {
+        let mut blocked: Vec<i32> = blocked
+            .iter()
+            .map(|&position| i32::from(Position::wrapping(position)))
+            .collect();
+        blocked.sort_unstable();
+        blocked.dedup();
+        assert!(
+            blocked.len() < DEFAULT_SIZE as usize,
+            "cannot block every position of the dial"
+        );
+        let mut knob = SafeDialKnob {
+            blocked,
+            ..SafeDialKnob::default()
+        };
+        knob.start = knob.skip_blocked(knob.start, Direction::Right);
+        knob.current_position = knob.start;
+        knob
+    }
+
+    /// Runs `commands` on a fresh dial and returns how often it came to rest at 0.
+    pub fn count_zero_landings(commands: &[RotationCommand]) -> u32 {
+        let mut safe_knob = SafeDialKnob::init();
+        safe_knob.apply_rotation_commands_solution_one(commands);
+        safe_knob.get_code_sequence()
+    }
+
+    /// Runs `commands` on a fresh dial and returns how many clicks pointed it at 0, failing on
+    /// the first rotation that would overflow.
+    pub fn count_zero_crossings(commands: &[RotationCommand]) -> anyhow::Result<u32> {
+        let mut safe_knob = SafeDialKnob::init();
+        safe_knob.apply_rotation_commands_solution_two(commands)?;
+        Ok(safe_knob.get_code_sequence())
+    }
+}
+
+impl<T: DialInteger> SafeDialKnob<T> {
+    /// Whether a zero-distance command counts the target it already points at. See
+    /// [`SafeDialKnobBuilder::count_initial_zero`].
+    fn counts_in_place(&self, command: &RotationCommand) -> bool {
+        self.count_initial_zero && command.distance == 0 && self.current_position == self.target
+    }
+
+    /// Zero events for a click that points the dial at the target under the dial's
+    /// [`ZeroCountPolicy`].
+    fn zeros_for_click(&self, last_click: bool) -> u32 {
+        match (self.zero_count_policy, last_click) {
+            (ZeroCountPolicy::MidOnly, true) => 0,
+            (ZeroCountPolicy::Both, true) => 2,
+            _ => 1,
+        }
+    }
+
+    /// The way `direction` actually turns this dial, given its [`Orientation`].
+    fn oriented(&self, direction: Direction) -> Direction {
+        match (self.orientation, direction) {
+            (Orientation::Inverted, Direction::Left) => Direction::Right,
+            (Orientation::Inverted, Direction::Right) => Direction::Left,
+            _ => direction,
+        }
+    }
+
+    /// Moves `position` on in `direction` past any blocked positions. An absolute jump
+    /// carries on to the right.
+    fn skip_blocked(&self, mut position: T, direction: Direction) -> T {
+        let direction = match direction {
+            Direction::Absolute => Direction::Right,
+            other => other,
+        };
+        while self.blocked.contains(&position) {
+            position = turn(position, &direction, 1, self.size);
+        }
+        position
+    }
+
+    /// Moves `distance` free positions on in `direction`, skipping blocked ones click by
+    /// click like solution two does. Whole laps of the free positions are dropped first.
+    fn step_over_blocked(&self, distance: i32, direction: Direction) -> T {
+        let free = self.size.to_i64().unwrap_or(i64::MAX) - self.blocked.len() as i64;
+        let steps = i64::from(distance).rem_euclid(free);
+        (0..steps).fold(self.current_position, |position, _| {
+            self.skip_blocked(turn(position, &direction, 1, self.size), direction)
+        })
+    }
+
+    fn rotate_knob_solution_two(&mut self, command: &RotationCommand) {
+        self.step_solution_two(command, &mut |_| {});
+    }
+
+    /// Solution-two rotation that reports the position after every click to `on_click`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `command` turns by a negative distance.
+    fn step_solution_two<F: FnMut(T)>(&mut self, command: &RotationCommand, on_click: &mut F) {
+        if command.direction == Direction::Absolute {
+            self.rotate_knob_solution_one(command);
+            return on_click(self.current_position);
+        }
+        assert!(
+            command.distance >= 0,
+            "negative distance in '{command}' has no solution-two clicks"
+        );
+        if self.counts_in_place(command) {
+            self.zero_position_occurrence += 1;
+        }
+
+        let mut current: T = self.current_position;
+        let direction = self.oriented(command.direction);
+        let mut steps: i32 = command.distance;
+
+        while steps != 0 {
+            current = self.skip_blocked(turn(current, &direction, 1, self.size), direction);
+            self.debug_assert_on_dial(current);
+
+            if current == self.target {
+                self.zero_position_occurrence += self.zeros_for_click(steps == 1);
+                log::trace!("dial crossed {current} during {command}");
+            }
+            on_click(current);
+
+            steps -= 1;
+        }
+        self.rest_at(command, current);
+    }
+
+    /// Solution-two rotation that reports overflow of the click or zero counters instead of
+    /// panicking. The dial is left untouched when an error is returned.
+    pub(crate) fn rotate_knob_checked(&mut self, command: &RotationCommand) -> anyhow::Result<()> {
+        if command.direction == Direction::Absolute {
+            self.rotate_knob_solution_one(command);
+            return Ok(());
+        }
+        if command.distance < 0 {
+            bail!("negative distance in '{command}' would overflow the click counter");
+        }
+
+        let mut current: T = self.current_position;
+        let mut zeros = self.zero_position_occurrence;
+        if self.counts_in_place(command) {
+            zeros = zeros
+                .checked_add(1)
+                .with_context(|| format!("zero count overflowed during '{command}'"))?;
+        }
+        let direction = self.oriented(command.direction);
+        for click in 1..=command.distance {
+            current = self.skip_blocked(turn(current, &direction, 1, self.size), direction);
+            self.debug_assert_on_dial(current);
+
+            if current == self.target {
+                zeros = zeros
+                    .checked_add(self.zeros_for_click(click == command.distance))
+                    .with_context(|| format!("zero count overflowed during '{command}'"))?;
+                log::trace!("dial crossed {current} during {command}");
+            }
+        }
+        self.zero_position_occurrence = zeros;
+        self.rest_at(command, current);
+        Ok(())
+    }
+
+    pub(crate) fn rotate_knob_solution_one(&mut self, command: &RotationCommand) {
+        let direction = self.oriented(command.direction);
+        let current = if self.blocked.is_empty() || direction == Direction::Absolute {
+            let current = turn(
+                self.current_position,
+                &direction,
+                command.distance,
+                self.size,
+            );
+            self.skip_blocked(current, direction)
+        } else {
+            self.step_over_blocked(command.distance, direction)
+        };
+
+        if current == self.target {
+            self.zero_position_occurrence += 1;
+        }
+
+        self.rest_at(command, current);
+    }
+
+    pub(crate) fn rotate(&mut self, command: &RotationCommand, mode: CountMode) {
+        match mode {
+            CountMode::Landings => self.rotate_knob_solution_one(command),
+            CountMode::Crossings => self.rotate_knob_solution_two(command),
+        }
+    }
+
+    /// Rotates by a signed step count: positive turns right and negative turns left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `signed_steps` is `i32::MIN`, which has no positive distance.
+    pub fn rotate_by(&mut self, signed_steps: i32, mode: CountMode) {
+        let direction = if signed_steps < 0 {
+            Direction::Left
+        } else {
+            Direction::Right
+        };
+        let distance = signed_steps
+            .checked_abs()
+            .expect("step count has a positive distance");
+        self.rotate(
+            &RotationCommand {
+                direction,
+                distance,
+            },
+            mode,
+        );
+    }
+
+    /// Consuming counterpart of a single rotation, for folding over commands.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance in [`CountMode::Crossings`].
+    pub fn rotated(mut self, command: &RotationCommand, mode: CountMode) -> Self {
+        self.rotate(command, mode);
+        self
+    }
+
+    /// Consuming counterpart of the `apply_rotation_commands_*` methods. Unlike
+    /// [`apply_rotation_commands_solution_two`](Self::apply_rotation_commands_solution_two)
+    /// it cannot fail, so in [`CountMode::Crossings`] it only suits input without negative
+    /// distances.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance in [`CountMode::Crossings`].
+    pub fn apply(self, commands: &[RotationCommand], mode: CountMode) -> Self {
+        commands
+            .iter()
+            .fold(self, |knob, command| knob.rotated(command, mode))
+    }
+
+    /// Applies owned commands as they are produced, e.g. straight from
+    /// [`commands_iter`](crate::commands_iter), without collecting them first.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance in [`CountMode::Crossings`].
+    pub fn apply_iter<I: IntoIterator<Item = RotationCommand>>(
+        &mut self,
+        commands: I,
+        mode: CountMode,
+    ) {
+        for command in commands {
+            self.rotate(&command, mode);
+        }
+    }
+
+    /// Streaming counterpart of [`apply`](SafeDialKnob::apply): applies one `command` as it
+    /// arrives and returns the running zero count.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance in [`CountMode::Crossings`].
+    pub fn push(&mut self, command: RotationCommand, mode: CountMode) -> u32 {
+        self.rotate(&command, mode);
+        self.zero_position_occurrence
+    }
+
+    /// Applies only `commands[i]` and returns how many zero events it contributed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds for `commands`, or on a negative distance in
+    /// [`CountMode::Crossings`].
+    pub fn apply_indexed(
+        &mut self,
+        commands: &[RotationCommand],
+        i: usize,
+        mode: CountMode,
+    ) -> u32 {
+        let before = self.zero_position_occurrence;
+        self.rotate(&commands[i], mode);
+        self.zero_position_occurrence - before
+    }
+
+    /// Trips in debug builds when `position` is not on the dial, e.g. after a `%` that
+    /// left a negative remainder.
+    fn debug_assert_on_dial(&self, position: T) {
+        debug_assert!(
+            position >= T::zero() && position < self.size,
+            "position {position} left the dial 0..{}",
+            self.size
+        );
+    }
+
+    fn rest_at(&mut self, command: &RotationCommand, position: T) {
+        self.debug_assert_on_dial(position);
+        self.current_position = position;
+        if let Some(history) = &mut self.history {
+            history.push(position);
+        }
+        log::debug!("{command} left the dial at {position}");
+    }
+
+    /// Applies `commands` with solution-two rules, stopping at the first rotation that
+    /// would overflow.
+    pub fn apply_rotation_commands_solution_two(
+        &mut self,
+        commands: &[RotationCommand],
+    ) -> anyhow::Result<()> {
+        commands
+            .iter()
+            .try_for_each(|command| self.rotate_knob_checked(command))
+    }
+
+    /// Fast path for input known to be bounded: non-negative distances and fewer than
+    /// `u32::MAX` zero clicks.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance, and on zero-count overflow in debug builds.
+    pub fn apply_rotation_commands_solution_two_unchecked(&mut self, commands: &[RotationCommand]) {
+        commands
+            .iter()
+            .for_each(|command| self.rotate_knob_solution_two(command));
+    }
+
+    /// Applies `commands` with solution-two stepping and calls `on_click` with the new
+    /// position after every single click. An absolute jump is one click.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a negative distance, which has no clicks.
+    pub fn apply_with_callback<F: FnMut(T)>(
+        &mut self,
+        commands: &[RotationCommand],
+        mut on_click: F,
+    ) {
+        commands
+            .iter()
+            .for_each(|command| self.step_solution_two(command, &mut on_click));
+    }
+
+    pub fn apply_rotation_commands_solution_one(&mut self, commands: &[RotationCommand]) {
+        commands
+            .iter()
+            .for_each(|command| self.rotate_knob_solution_one(command));
+    }
+
+    /// Consumes the dial and returns the final zero count, i.e. the puzzle's answer.
+    pub fn get_code_sequence(self) -> u32 {
+        self.zero_position_occurrence
+    }
+
+    pub fn current_position(&self) -> T {
+        self.current_position
+    }
+
+    /// Zero events counted so far, for peeking in the middle of a run.
+    pub fn zero_count(&self) -> u32 {
+        self.zero_position_occurrence
+    }
+
+    /// Points the dial straight at `position` without counting anything or recording
+    /// history. Fails when `position` is not on the dial.
+    pub fn set_position(&mut self, position: i32) -> anyhow::Result<()> {
+        match num_traits::cast::<i32, T>(position) {
+            Some(position) if position >= T::zero() && position < self.size => {
+                self.current_position = position;
+                Ok(())
+            }
+            _ => bail!("position {position} is outside the dial 0..{}", self.size),
+        }
+    }
+
+    /// Whether the dial points at the position it was configured to start from.
+    pub fn returned_to_start(&self) -> bool {
+        self.current_position == self.start
+    }
+
+    /// Resting positions recorded so far, starting with the initial one. Empty unless the
+    /// dial was built with history recording enabled.
+    pub fn history(&self) -> &[T] {
+        self.history.as_deref().unwrap_or_default()
+    }
+
+    /// Reverts `command`, which must be the last command applied to the dial.
+    ///
+    /// A zero landing is only taken back when the dial currently rests at 0, so the
+    /// result is exact for solution-one semantics only; clicks that passed over 0 during
+    /// a solution-two rotation are not subtracted. Blocked positions are stepped over on the
+    /// way back just as on the way there. An absolute jump cannot be reverted and leaves the
+    /// dial where it is.
+    pub fn undo(&mut self, command: &RotationCommand) {
+        let Some(back) = self.oriented(command.direction).opposite() else {
+            return;
+        };
+        if self.current_position == self.target {
+            self.zero_position_occurrence = self.zero_position_occurrence.saturating_sub(1);
+        }
+        if let Some(history) = &mut self.history {
+            history.pop();
+        }
+        self.current_position = if self.blocked.is_empty() {
+            turn(self.current_position, &back, command.distance, self.size)
+        } else {
+            self.step_over_blocked(command.distance, back)
+        };
+    }
+
+    /// Draws the dial as a ruler of its positions with a caret under the one it points at.
+    pub fn render(&self) -> String {
+        let size = self.size.to_usize().unwrap_or_default();
+        let ruler: String = (0..size).map(|position| format!("{position:>3}")).collect();
+        let caret_column = self.current_position.to_usize().unwrap_or_default() * 3 + 2;
+        format!("{ruler}\n{:>width$}", "^", width = caret_column + 1)
+    }
+}
+
+/// Iterates the recorded [`history`](SafeDialKnob::history), which is empty unless
+/// recording was enabled.
+impl<'a, T: DialInteger> IntoIterator for &'a SafeDialKnob<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.history().iter()
+    }
+}
+
+impl<T: DialInteger> Display for SafeDialKnob<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SafeDialKnob(pos={}, zeros={})",
+            self.current_position, self.zero_position_occurrence
+        )
+    }
+}
+
+/// Collects the configuration of a [`SafeDialKnob`] and checks it for consistency.
+#[derive(Debug, Clone)]
+pub struct SafeDialKnobBuilder {
+    size: u32,
+    start: i32,
+    target: i32,
+    record_history: bool,
+    count_initial_zero: bool,
+    zero_count_policy: ZeroCountPolicy,
+}
+
+impl Default for SafeDialKnobBuilder {
+    fn default() -> Self {
+        SafeDialKnobBuilder {
+            size: DEFAULT_SIZE,
+            start: DEFAULT_START,
+            target: 0,
+            record_history: false,
+            count_initial_zero: false,
+            zero_count_policy: ZeroCountPolicy::default(),
+        }
+    }
+}
+
+impl SafeDialKnobBuilder {
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn start(mut self, start: i32) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn target(mut self, target: i32) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn record_history(mut self, record_history: bool) -> Self {
+        self.record_history = record_history;
+        self
+    }
+
+    /// Makes a zero-distance command such as `L0` count once, in solution-two mode, when the
+    /// dial already points at the target. Without it a command that makes no click never
+    /// counts, which is the puzzle's behaviour.
+    pub fn count_initial_zero(mut self, count_initial_zero: bool) -> Self {
+        self.count_initial_zero = count_initial_zero;
+        self
+    }
+
+    pub fn zero_count_policy(mut self, zero_count_policy: ZeroCountPolicy) -> Self {
+        self.zero_count_policy = zero_count_policy;
+        self
+    }
+
+    pub fn build(self) -> anyhow::Result<SafeDialKnob> {
+        if self.size == 0 {
+            bail!("dial size must be positive");
+        }
+        let size = i32::try_from(self.size)
+            .with_context(|| format!("dial size {} does not fit an i32 position", self.size))?;
+        let start = self.start.rem_euclid(size);
+
+        Ok(SafeDialKnob {
+            current_position: start,
+            zero_position_occurrence: 0,
+            start,
+            size,
+            target: self.target.rem_euclid(size),
+            history: self.record_history.then(|| vec![start]),
+            count_initial_zero: self.count_initial_zero,
+            zero_count_policy: self.zero_count_policy,
+            blocked: Vec::new(),
+            orientation: Orientation::default(),
+        })
+    }
+}
+
+/// Counts how many clicks of `command`, started at `start` on a dial of `size` positions,
+/// point the dial at 0, including the click it comes to rest on. Runs in constant time.
+///
+/// An absolute jump is a single move and counts once if it lands on 0.
+///
+/// # Panics
+///
+/// Panics if `size` is 0, or if a left or right turn has a negative distance, which has no
+/// solution-two clicks just as on the dial itself.
+pub fn crossings_for(start: i32, command: &RotationCommand, size: u32) -> u32 {
+    let size = i64::from(size);
+    if command.direction == Direction::Absolute {
+        return u32::from(i64::from(command.distance).rem_euclid(size) == 0);
+    }
+    assert!(
+        command.distance >= 0,
+        "negative distance in '{command}' has no solution-two clicks"
+    );
+    let start = i64::from(start).rem_euclid(size);
+    let rightward = command.direction == Direction::Right;
+    let distance = i64::from(command.distance);
+
+    let crossings = if rightward {
+        (start + distance) / size
+    } else if start == 0 {
+        distance / size
+    } else if distance >= start {
+        (distance - start) / size + 1
+    } else {
+        0
+    };
+    u32::try_from(crossings).unwrap_or(u32::MAX)
+}
+
+/// Final position of a dial of `size` positions that starts at `start` and runs `commands`,
+/// computed with plain modular arithmetic and no zero bookkeeping.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn fast_forward(start: i32, commands: &[RotationCommand], size: u32) -> i32 {
+    let size = i64::from(size);
+    let end = commands.iter().fold(i64::from(start), |position, command| {
+        let distance = i64::from(command.distance);
+        let moved = match command.direction {
+            Direction::Right => position + distance,
+            Direction::Left => position - distance,
+            Direction::Absolute => distance,
+        };
+        moved.rem_euclid(size)
+    });
+    end.rem_euclid(size) as i32
+}
+
+/// Where a dial of `size` positions pointing at `start` ends after turning `net` steps in
+/// total, positive to the right. Being a `const fn`, it can check positions at compile time.
+///
+/// # Panics
+///
+/// Panics if `size` is not positive.
+pub const fn final_position(start: i32, net: i32, size: i32) -> i32 {
+    assert!(size > 0, "dial size must be positive");
+    (start as i64 + net as i64).rem_euclid(size as i64) as i32
+}
+
+/// Where the dial of `size` positions points after the first `clicks_in` clicks of `command`,
+/// started at `start`. The offset is clamped to the command's distance, and an absolute jump
+/// takes its one click straight to the destination.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+pub fn position_at_click(start: i32, command: &RotationCommand, clicks_in: i32, size: u32) -> i32 {
+    let size = i64::from(size);
+    let start = i64::from(start).rem_euclid(size);
+    let distance = i64::from(command.distance);
+    let position = if command.direction == Direction::Absolute {
+        if clicks_in > 0 { distance } else { start }
+    } else {
+        let clicks = i64::from(clicks_in).clamp(0, distance.abs());
+        let rightward = (command.direction == Direction::Right) == (distance >= 0);
+        if rightward {
+            start + clicks
+        } else {
+            start - clicks
+        }
+    };
+    position.rem_euclid(size) as i32
+}
+
+/// Moves `current` by `steps` clicks in `direction`, wrapping around a dial of `size`. For an
+/// absolute direction `steps` is the destination instead.
+///
+/// The step count is reduced onto the dial first and every branch stays below `size`,
+/// which keeps the arithmetic valid for unsigned position types too.
+pub(crate) fn turn<T: DialInteger>(current: T, direction: &Direction, steps: i32, size: T) -> T {
+    let wide_size = size.to_i64().unwrap_or(i64::MAX);
+    let steps: T = dial_integer(i64::from(steps).rem_euclid(wide_size));
+    let back = size - steps;
+    match direction {
+        Direction::Right if current < back => current + steps,
+        Direction::Right => current - back,
+        Direction::Left if current >= steps => current - steps,
+        Direction::Left => current + back,
+        Direction::Absolute => steps,
+    }
+}
+
+fn dial_integer<T: DialInteger>(value: i64) -> T {
+    num_traits::cast(value).expect("dial values fit the dial's position type")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{
+        SAMPLE_INPUT, commands_iter, load_rotation_commands, parse_commands, sample_commands,
+    };
+
+    #[test]
+    fn test_solution_one_small_puzzle_input() {
+        let first_expected_answer = 3;
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
+
+        assert_eq!(first_expected_answer, safe.get_code_sequence());
+    }
+
+    #[test]
+    fn test_solution_two_small_puzzle_input() {
+        let second_expected_answer = 6;
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+
+        assert_eq!(second_expected_answer, safe.get_code_sequence());
+    }
+
+    #[test]
+    fn test_solution_one_puzzle_input() {
+        let first_star_answer = 1135;
+        let test_puzzle_input = load_rotation_commands("puzzle_input").unwrap();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
+
+        assert_eq!(first_star_answer, safe.get_code_sequence());
+    }
+
+    #[test]
+    fn test_solution_two_puzzle_input() {
+        let second_start_answer = 6558;
+        let test_puzzle_input = load_rotation_commands("puzzle_input").unwrap();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+
+        assert_eq!(second_start_answer, safe.get_code_sequence());
+    }
+
+    #[test]
+    fn test_count_zero_landings_and_crossings() {
+        let test_puzzle_input = sample_commands();
+
+        assert_eq!(3, SafeDialKnob::count_zero_landings(&test_puzzle_input));
+        assert_eq!(
+            6,
+            SafeDialKnob::count_zero_crossings(&test_puzzle_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zero_count_peeks_mid_run() {
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input[..3]);
+        assert_eq!(safe.zero_count(), 1);
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input[3..]);
+        assert_eq!(safe.zero_count(), 3);
+        assert_eq!(safe.get_code_sequence(), 3);
+    }
+
+    #[test]
+    fn test_solutions_with_wider_and_unsigned_positions() {
+        let test_puzzle_input = sample_commands();
+
+        let mut safe = SafeDialKnob::<i64>::default();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
+        assert_eq!(3, safe.get_code_sequence());
+
+        let mut safe = SafeDialKnob::<i64>::default();
+        safe.apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+        assert_eq!(6, safe.get_code_sequence());
+
+        let mut safe = SafeDialKnob::<u32>::default();
+        safe.apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+        assert_eq!(32, safe.current_position());
+        assert_eq!(6, safe.get_code_sequence());
+    }
+
+    #[test]
+    fn test_functional_apply_matches_mutating_path() {
+        let test_puzzle_input = sample_commands();
+
+        let mut mutated = SafeDialKnob::init();
+        mutated.apply_rotation_commands_solution_one(&test_puzzle_input);
+        let folded = test_puzzle_input
+            .iter()
+            .fold(SafeDialKnob::init(), |knob, command| {
+                knob.rotated(command, CountMode::Landings)
+            });
+        assert_eq!(folded, mutated);
+
+        let mut mutated = SafeDialKnob::init();
+        mutated
+            .apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+        let applied = SafeDialKnob::init().apply(&test_puzzle_input, CountMode::Crossings);
+        assert_eq!(applied, mutated);
+        assert_eq!(applied.get_code_sequence(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative distance in 'R-5'")]
+    fn test_apply_rejects_negative_crossings() {
+        let negative = RotationCommand::parse("R-5").unwrap();
+        let landed = SafeDialKnob::init().rotated(&negative, CountMode::Landings);
+        assert_eq!(landed.current_position(), 45);
+
+        SafeDialKnob::init().apply(&[negative], CountMode::Crossings);
+    }
+
+    #[test]
+    fn test_rotate_by_signed_steps() {
+        let mut safe = SafeDialKnob::init();
+        safe.rotate_by(-68, CountMode::Crossings);
+        assert_eq!(safe.current_position(), 82);
+        assert_eq!(safe.zero_count(), 1);
+
+        safe.rotate_by(18, CountMode::Landings);
+        assert_eq!(safe.current_position(), 0);
+        assert_eq!(safe.zero_count(), 2);
+
+        safe.rotate_by(0, CountMode::Landings);
+        assert_eq!(safe.current_position(), 0);
+    }
+
+    #[test]
+    fn test_apply_iter_from_parse_iterator() {
+        for (mode, expected) in [(CountMode::Landings, 3), (CountMode::Crossings, 6)] {
+            let mut safe = SafeDialKnob::init();
+            safe.apply_iter(
+                commands_iter(SAMPLE_INPUT.as_bytes()).map(|command| command.unwrap()),
+                mode,
+            );
+            assert_eq!(safe.get_code_sequence(), expected);
+        }
+    }
+
+    #[test]
+    fn test_push_reports_running_count() {
+        let mut landings = SafeDialKnob::init();
+        let mut crossings = SafeDialKnob::init();
+        let mut counts = Vec::new();
+        for command in sample_commands() {
+            counts.push((
+                landings.push(command, CountMode::Landings),
+                crossings.push(command, CountMode::Crossings),
+            ));
+        }
+
+        assert_eq!(
+            counts,
+            [
+                (0, 1),
+                (0, 1),
+                (1, 2),
+                (1, 2),
+                (1, 3),
+                (2, 4),
+                (2, 4),
+                (3, 5),
+                (3, 5),
+                (3, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_with_callback_sees_every_click() {
+        let test_puzzle_input = sample_commands();
+        let mut seen = Vec::new();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_with_callback(&test_puzzle_input, |position| seen.push(position));
+
+        assert_eq!(seen, crate::analysis::clicks(&test_puzzle_input));
+        assert_eq!(seen.len(), 462);
+        assert_eq!(seen.iter().filter(|&&position| position == 0).count(), 6);
+        assert_eq!(safe.get_code_sequence(), 6);
+    }
+
+    #[test]
+    fn test_apply_indexed_reports_delta() {
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input[..2]);
+
+        assert_eq!(
+            safe.apply_indexed(&test_puzzle_input, 2, CountMode::Landings),
+            1
+        );
+        assert_eq!(
+            safe.apply_indexed(&test_puzzle_input, 3, CountMode::Landings),
+            0
+        );
+        assert_eq!(safe.zero_count(), 1);
+
+        let mut safe = SafeDialKnob::init();
+        let deltas: u32 = (0..test_puzzle_input.len())
+            .map(|i| safe.apply_indexed(&test_puzzle_input, i, CountMode::Crossings))
+            .sum();
+        assert_eq!(deltas, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_apply_indexed_rejects_out_of_range_index() {
+        SafeDialKnob::init().apply_indexed(&sample_commands(), 10, CountMode::Landings);
+    }
+
+    #[test]
+    fn test_reduced_commands_end_on_same_position() {
+        let mut commands = sample_commands();
+        commands.extend(parse_commands("R1003\nL250\nR100\n").unwrap());
+        let reduced: Vec<RotationCommand> = commands
+            .iter()
+            .map(|command| command.reduced(DEFAULT_SIZE))
+            .collect();
+
+        let original = SafeDialKnob::init().apply(&commands, CountMode::Landings);
+        let shortened = SafeDialKnob::init().apply(&reduced, CountMode::Landings);
+        assert_eq!(original.current_position(), shortened.current_position());
+        assert_eq!(original.zero_count(), shortened.zero_count());
+
+        let original = SafeDialKnob::init().apply(&commands, CountMode::Crossings);
+        let shortened = SafeDialKnob::init().apply(&reduced, CountMode::Crossings);
+        assert_eq!(original.current_position(), shortened.current_position());
+        assert!(original.zero_count() > shortened.zero_count());
+    }
+
+    #[test]
+    fn test_positions_stay_on_dial_in_debug_builds() {
+        let test_puzzle_input = sample_commands();
+        for mode in [CountMode::Landings, CountMode::Crossings] {
+            SafeDialKnob::init().apply(&test_puzzle_input, mode);
+            SafeDialKnob::<u32>::default().apply(&test_puzzle_input, mode);
+        }
+        SafeDialKnob::init()
+            .apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+    }
+
+    /// The original `(50 - 68) % 100` arithmetic left the dial at -18.
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "position -18 left the dial")]
+    fn test_negative_remainder_trips_debug_assertion() {
+        let safe = SafeDialKnob::init();
+        let distance = 68;
+        safe.debug_assert_on_dial((safe.current_position() - distance) % safe.size);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative distance in 'R-5' has no solution-two clicks")]
+    fn test_unchecked_rotation_rejects_negative_distance() {
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_two_unchecked(&[
+            RotationCommand::parse("R-5").unwrap()
+        ]);
+    }
+
+    #[test]
+    fn test_checked_rotation_reports_overflow() {
+        let mut safe = SafeDialKnob::init();
+        let negative = RotationCommand::parse("R-5").unwrap();
+        assert!(
+            safe.apply_rotation_commands_solution_two(&[negative])
+                .is_err()
+        );
+        assert_eq!(safe, SafeDialKnob::init());
+
+        safe.zero_position_occurrence = u32::MAX;
+        let wrapping = RotationCommand::parse("R1000").unwrap();
+        assert!(
+            safe.apply_rotation_commands_solution_two(&[wrapping])
+                .is_err()
+        );
+        assert_eq!(safe.current_position(), 50);
+    }
+
+    #[test]
+    fn test_zero_crossing_is_traced() {
+        testing_logger::setup();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_two(&[RotationCommand::parse("L68").unwrap()])
+            .unwrap();
+
+        testing_logger::validate(|captured_logs| {
+            let traces: Vec<_> = captured_logs
+                .iter()
+                .filter(|log| log.level == log::Level::Trace)
+                .collect();
+            assert_eq!(traces.len(), 1);
+            assert_eq!(traces[0].body, "dial crossed 0 during L68");
+            assert!(
+                captured_logs
+                    .iter()
+                    .any(|log| log.level == log::Level::Debug
+                        && log.body == "L68 left the dial at 82")
+            );
+        });
+    }
+
+    #[test]
+    fn test_undo_restores_previous_state() {
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input[..2]);
+        let snapshot = safe.clone();
+
+        let landing_on_zero = &test_puzzle_input[2];
+        safe.apply_rotation_commands_solution_one(std::slice::from_ref(landing_on_zero));
+        assert_eq!(safe.zero_position_occurrence, 1);
+
+        safe.undo(landing_on_zero);
+        assert_eq!(safe, snapshot);
+
+        let jump = RotationCommand::parse("A0").unwrap();
+        safe.apply_rotation_commands_solution_one(std::slice::from_ref(&jump));
+        let after_jump = safe.clone();
+        safe.undo(&jump);
+        assert_eq!(safe, after_jump);
+    }
+
+    #[test]
+    fn test_set_position() {
+        let mut safe = SafeDialKnob::init();
+        safe.rotate_by(50, CountMode::Landings);
+        safe.set_position(99).unwrap();
+        assert_eq!(safe.current_position(), 99);
+        assert_eq!(safe.zero_count(), 1);
+
+        let error = safe.set_position(100).unwrap_err();
+        assert_eq!(error.to_string(), "position 100 is outside the dial 0..100");
+        assert!(safe.set_position(-1).is_err());
+        assert_eq!(safe.current_position(), 99);
+
+        let mut unsigned = SafeDialKnob::<u32>::default();
+        assert!(unsigned.set_position(-1).is_err());
+        unsigned.set_position(7).unwrap();
+        assert_eq!(unsigned.current_position(), 7);
+    }
+
+    #[test]
+    fn test_returned_to_start() {
+        let mut safe = SafeDialKnob::init();
+        let round_trip = parse_commands("R25\nL125\nR100\n").unwrap();
+        safe.apply_rotation_commands_solution_one(&round_trip);
+        assert!(safe.returned_to_start());
+
+        let mut safe = SafeDialKnob::builder().start(10).build().unwrap();
+        safe.apply_rotation_commands_solution_one(&sample_commands());
+        assert!(!safe.returned_to_start());
+    }
+
+    #[test]
+    fn test_absolute_command() {
+        let a37 = RotationCommand::parse("A37").unwrap();
+
+        let mut safe = SafeDialKnob::builder().start(12).build().unwrap();
+        safe.apply_rotation_commands_solution_one(std::slice::from_ref(&a37));
+        assert_eq!(safe.current_position(), 37);
+        assert_eq!(safe.zero_count(), 0);
+
+        let mut safe = SafeDialKnob::builder()
+            .start(12)
+            .target(37)
+            .build()
+            .unwrap();
+        safe.apply_rotation_commands_solution_two(std::slice::from_ref(&a37))
+            .unwrap();
+        assert_eq!(safe.current_position(), 37);
+        assert_eq!(safe.zero_count(), 1);
+
+        let commands = parse_commands("A0\nA137\nL37\n").unwrap();
+        assert_eq!(SafeDialKnob::count_zero_crossings(&commands).unwrap(), 2);
+        assert_eq!(crossings_for(12, &commands[0], DEFAULT_SIZE), 1);
+    }
+
+    #[test]
+    fn test_zero_distance_counts_initial_zero_only_when_enabled() {
+        let l0 = RotationCommand::parse("L0").unwrap();
+        let count = |start: i32, count_initial_zero: bool| {
+            let mut safe = SafeDialKnob::builder()
+                .start(start)
+                .count_initial_zero(count_initial_zero)
+                .build()
+                .unwrap();
+            safe.apply_rotation_commands_solution_two(std::slice::from_ref(&l0))
+                .unwrap();
+            assert_eq!(safe.current_position(), start);
+            safe.get_code_sequence()
+        };
+
+        assert_eq!(count(0, true), 1);
+        assert_eq!(count(0, false), 0);
+        assert_eq!(count(5, true), 0);
+        assert_eq!(count(5, false), 0);
+
+        let mut safe = SafeDialKnob::builder()
+            .start(0)
+            .count_initial_zero(true)
+            .build()
+            .unwrap();
+        safe.apply_rotation_commands_solution_two_unchecked(std::slice::from_ref(&l0));
+        assert_eq!(safe.get_code_sequence(), 1);
+    }
+
+    #[test]
+    fn test_zero_count_policies_on_sample() {
+        let test_puzzle_input = sample_commands();
+        for (policy, expected) in [
+            (ZeroCountPolicy::EndInclusive, 6),
+            (ZeroCountPolicy::MidOnly, 3),
+            (ZeroCountPolicy::Both, 9),
+        ] {
+            let knob = || {
+                SafeDialKnob::builder()
+                    .zero_count_policy(policy)
+                    .build()
+                    .unwrap()
+            };
+
+            let mut checked = knob();
+            checked
+                .apply_rotation_commands_solution_two(&test_puzzle_input)
+                .unwrap();
+            assert_eq!(checked.get_code_sequence(), expected, "{policy:?}");
+
+            let mut unchecked = knob();
+            unchecked.apply_rotation_commands_solution_two_unchecked(&test_puzzle_input);
+            assert_eq!(unchecked.get_code_sequence(), expected, "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn test_inverted_orientation_mirrors_positions() {
+        let test_puzzle_input = sample_commands();
+        let positions = |orientation| {
+            let mut safe = SafeDialKnob::with_orientation(orientation);
+            let mut positions = vec![safe.current_position()];
+            for command in &test_puzzle_input {
+                safe.rotate(command, CountMode::Landings);
+                positions.push(safe.current_position());
+            }
+            (positions, safe.get_code_sequence())
+        };
+
+        let (standard, standard_zeros) = positions(Orientation::Standard);
+        let (inverted, inverted_zeros) = positions(Orientation::Inverted);
+        assert_eq!(inverted, [50, 18, 48, 0, 5, 45, 0, 1, 0, 86, 68]);
+        let mirrored: Vec<i32> = standard.iter().map(|p| (100 - p) % 100).collect();
+        assert_eq!(inverted, mirrored);
+        assert_eq!(inverted_zeros, standard_zeros);
+
+        let safe = SafeDialKnob::with_orientation(Orientation::Inverted)
+            .apply(&test_puzzle_input, CountMode::Crossings);
+        assert_eq!(safe.current_position(), 68);
+        assert_eq!(safe.get_code_sequence(), 6);
+    }
+
+    #[test]
+    fn test_blocked_positions_are_skipped() {
+        let test_puzzle_input = sample_commands();
+
+        let blocked_zero = SafeDialKnob::with_blocked(&[0]);
+        for mode in [CountMode::Landings, CountMode::Crossings] {
+            let safe = blocked_zero.clone().apply(&test_puzzle_input, mode);
+            assert_eq!(safe.zero_count(), 0, "{mode:?}");
+        }
+        let mut checked = blocked_zero.clone();
+        checked
+            .apply_rotation_commands_solution_two(&test_puzzle_input)
+            .unwrap();
+        assert_eq!(checked.zero_count(), 0);
+
+        let r1 = RotationCommand::parse("R1").unwrap();
+        let l1 = RotationCommand::parse("L1").unwrap();
+        let safe = SafeDialKnob::with_blocked(&[51, 152]);
+        assert_eq!(
+            safe.clone()
+                .apply(std::slice::from_ref(&r1), CountMode::Crossings)
+                .current_position(),
+            53
+        );
+        assert_eq!(
+            safe.clone()
+                .apply(std::slice::from_ref(&r1), CountMode::Landings)
+                .current_position(),
+            53
+        );
+        assert_eq!(
+            safe.apply(&[r1, l1], CountMode::Crossings)
+                .current_position(),
+            50
+        );
+
+        // R3 from 50 passes over the blocked 51 without counting it, in both modes.
+        let r3 = RotationCommand::parse("R3").unwrap();
+        let safe = SafeDialKnob::with_blocked(&[51]);
+        for mode in [CountMode::Landings, CountMode::Crossings] {
+            assert_eq!(
+                safe.clone()
+                    .apply(std::slice::from_ref(&r3), mode)
+                    .current_position(),
+                54,
+                "{mode:?}"
+            );
+        }
+        let mut undone = safe
+            .clone()
+            .apply(std::slice::from_ref(&r3), CountMode::Landings);
+        undone.undo(&r3);
+        assert_eq!(undone.current_position(), 50);
+
+        let blocked_start = SafeDialKnob::with_blocked(&[50, 51]);
+        assert_eq!(blocked_start.current_position(), 52);
+        assert_eq!(
+            blocked_start
+                .apply(std::slice::from_ref(&r1), CountMode::Landings)
+                .current_position(),
+            53
+        );
+
+        // 98 free positions: a lap and a bit ends where stepping click by click does.
+        let safe = SafeDialKnob::with_blocked(&[51, 60]);
+        for line in ["R101", "L25"] {
+            let command = RotationCommand::parse(line).unwrap();
+            assert_eq!(
+                safe.clone()
+                    .apply(std::slice::from_ref(&command), CountMode::Landings)
+                    .current_position(),
+                safe.clone()
+                    .apply(std::slice::from_ref(&command), CountMode::Crossings)
+                    .current_position(),
+                "{line}"
+            );
+        }
+        let l_minus_3 = RotationCommand::parse("L-3").unwrap();
+        assert_eq!(
+            safe.apply(std::slice::from_ref(&l_minus_3), CountMode::Landings)
+                .current_position(),
+            54
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot block every position")]
+    fn test_blocking_every_position_panics() {
+        let every: Vec<i32> = (0..100).collect();
+        SafeDialKnob::with_blocked(&every);
+    }
+
+    #[test]
+    fn test_crossings_for() {
+        let r1000 = RotationCommand::parse("R1000").unwrap();
+        assert_eq!(crossings_for(50, &r1000, 100), 10);
+
+        let l50 = RotationCommand::parse("L50").unwrap();
+        assert_eq!(crossings_for(50, &l50, 100), 1);
+        assert_eq!(crossings_for(0, &l50, 100), 0);
+        assert_eq!(crossings_for(49, &l50, 100), 1);
+        assert_eq!(crossings_for(51, &l50, 100), 0);
+
+        let r25 = RotationCommand::parse("R25").unwrap();
+        assert_eq!(crossings_for(5, &r25, 10), 3);
+
+        let negative = RotationCommand::parse("R-5").unwrap();
+        assert!(std::panic::catch_unwind(|| crossings_for(3, &negative, 100)).is_err());
+
+        let mut position = DEFAULT_START;
+        let mut total = 0;
+        for command in sample_commands() {
+            total += crossings_for(position, &command, DEFAULT_SIZE);
+            position = turn(position, &command.direction, command.distance, 100);
+        }
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn test_position_range() {
+        assert_eq!(Position::new(100), None);
+        assert_eq!(Position::new(-1), None);
+        assert_eq!(Position::new(99).map(i32::from), Some(99));
+        assert_eq!(i32::from(Position::wrapping(-1)), 99);
+
+        let safe = SafeDialKnob::init().apply(&sample_commands(), CountMode::Landings);
+        assert_eq!(safe.position(), Position::new(32));
+        assert_eq!(
+            SafeDialKnob::resume_from(-1, 0).position(),
+            Position::new(99)
+        );
+
+        let mut wide = SafeDialKnob::builder().size(200).build().unwrap();
+        wide.set_position(150).unwrap();
+        assert_eq!(wide.position(), None);
+    }
+
+    // The sample turns 122 steps right and 340 left, resting on 32.
+    const _: () = assert!(final_position(DEFAULT_START, 122 - 340, DEFAULT_SIZE as i32) == 32);
+
+    #[test]
+    fn test_final_position() {
+        let net: i32 = sample_commands()
+            .iter()
+            .map(|command| match command.direction {
+                Direction::Left => -command.distance,
+                _ => command.distance,
+            })
+            .sum();
+
+        assert_eq!(net, -218);
+        assert_eq!(final_position(DEFAULT_START, net, DEFAULT_SIZE as i32), 32);
+        assert_eq!(final_position(i32::MAX, i32::MAX, 100), 94);
+    }
+
+    #[test]
+    fn test_fast_forward_matches_solution_one() {
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
+
+        assert_eq!(
+            fast_forward(DEFAULT_START, &test_puzzle_input, DEFAULT_SIZE),
+            safe.current_position()
+        );
+        assert_eq!(fast_forward(-1, &[], DEFAULT_SIZE), 99);
+
+        let puzzle_input = load_rotation_commands("puzzle_input").unwrap();
+        let safe = SafeDialKnob::init().apply(&puzzle_input, CountMode::Landings);
+        assert_eq!(
+            fast_forward(DEFAULT_START, &puzzle_input, DEFAULT_SIZE),
+            safe.current_position()
+        );
+    }
+
+    #[test]
+    fn test_position_at_click() {
+        let r3 = RotationCommand::parse("R3").unwrap();
+        assert_eq!(position_at_click(98, &r3, 0, 100), 98);
+        assert_eq!(position_at_click(98, &r3, 1, 100), 99);
+        assert_eq!(position_at_click(98, &r3, 2, 100), 0);
+        assert_eq!(position_at_click(98, &r3, 50, 100), 1);
+        assert_eq!(position_at_click(98, &r3, -4, 100), 98);
+
+        let l12 = RotationCommand::parse("L12").unwrap();
+        assert_eq!(position_at_click(5, &l12, 7, 10), 8);
+
+        let a37 = RotationCommand::parse("A37").unwrap();
+        assert_eq!(position_at_click(5, &a37, 1, 100), 37);
+    }
+
+    #[test]
+    fn test_default_dial_uses_default_constants() {
+        let safe = SafeDialKnob::<i32>::default();
+
+        assert_eq!(safe.current_position, DEFAULT_START);
+        assert_eq!(safe.size, DEFAULT_SIZE as i32);
+    }
+
+    #[test]
+    fn test_resume_from_matches_single_pass() {
+        let test_puzzle_input = sample_commands();
+        let (head, tail) = test_puzzle_input.split_at(5);
+
+        let mut first_chunk = SafeDialKnob::init();
+        first_chunk.apply_rotation_commands_solution_one(head);
+        let mut second_chunk =
+            SafeDialKnob::resume_from(first_chunk.current_position(), first_chunk.zero_count());
+        second_chunk.apply_rotation_commands_solution_one(tail);
+        assert_eq!(second_chunk.get_code_sequence(), 3);
+
+        let mut first_chunk = SafeDialKnob::init();
+        first_chunk
+            .apply_rotation_commands_solution_two(head)
+            .unwrap();
+        let mut second_chunk =
+            SafeDialKnob::resume_from(first_chunk.current_position(), first_chunk.zero_count());
+        second_chunk
+            .apply_rotation_commands_solution_two(tail)
+            .unwrap();
+        assert_eq!(second_chunk.get_code_sequence(), 6);
+    }
+
+    #[test]
+    fn test_positions_are_normalized_on_construction() {
+        assert_eq!(SafeDialKnob::resume_from(-1, 0).current_position(), 99);
+        assert_eq!(SafeDialKnob::resume_from(150, 2).current_position(), 50);
+        assert_eq!(SafeDialKnob::resume_from(150, 2).zero_count(), 2);
+
+        let safe = SafeDialKnob::builder()
+            .start(-1)
+            .target(150)
+            .build()
+            .unwrap();
+        assert_eq!(safe.current_position(), 99);
+        assert_eq!(safe.target, 50);
+        assert!(safe.returned_to_start());
+    }
+
+    #[test]
+    fn test_builder_initial_state() {
+        assert_eq!(
+            SafeDialKnob::builder().build().unwrap(),
+            SafeDialKnob::init()
+        );
+
+        let safe = SafeDialKnob::builder()
+            .size(10)
+            .start(3)
+            .target(7)
+            .record_history(true)
+            .build()
+            .unwrap();
+        assert_eq!(safe.current_position(), 3);
+        assert_eq!(safe.size, 10);
+        assert_eq!(safe.target, 7);
+        assert_eq!(safe.zero_count(), 0);
+        assert_eq!(safe.history(), [3]);
+    }
+
+    #[test]
+    fn test_try_new_checks_bounds() {
+        let safe = SafeDialKnob::try_new(10, 3, 7).unwrap();
+        assert_eq!(safe.current_position(), 3);
+        assert_eq!(safe.target, 7);
+        assert_eq!(
+            SafeDialKnob::try_new(100, 50, 0).unwrap(),
+            SafeDialKnob::init()
+        );
+
+        assert!(SafeDialKnob::try_new(0, 0, 0).is_err());
+        assert!(SafeDialKnob::try_new(10, -1, 0).is_err());
+        assert!(SafeDialKnob::try_new(10, 10, 0).is_err());
+        assert!(SafeDialKnob::try_new(10, 0, -1).is_err());
+        assert!(SafeDialKnob::try_new(10, 0, 10).is_err());
+        assert!(SafeDialKnob::try_new(u32::MAX, 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_builder_rejects_inconsistent_configuration() {
+        assert!(SafeDialKnob::builder().size(0).build().is_err());
+        assert!(SafeDialKnob::builder().size(u32::MAX).build().is_err());
+    }
+
+    #[test]
+    fn test_iterating_a_knob_walks_its_history() {
+        let mut safe = SafeDialKnob::builder()
+            .record_history(true)
+            .build()
+            .unwrap();
+        safe.apply_rotation_commands_solution_one(&sample_commands());
+
+        let mut positions = Vec::new();
+        for position in &safe {
+            positions.push(*position);
+        }
+        assert_eq!(positions, [50, 82, 52, 0, 95, 55, 0, 99, 0, 14, 32]);
+
+        assert_eq!((&SafeDialKnob::init()).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_builder_configured_dial_rotates() {
+        let mut safe = SafeDialKnob::builder()
+            .size(10)
+            .start(5)
+            .target(2)
+            .record_history(true)
+            .build()
+            .unwrap();
+        let commands = parse_commands("R7\nL13\nR25\n").unwrap();
+        safe.apply_rotation_commands_solution_one(&commands);
+
+        assert_eq!(safe.history(), [5, 2, 9, 4]);
+        assert_eq!(safe.get_code_sequence(), 1);
+    }
+
+    #[test]
+    fn test_display_summarizes_state() {
+        let mut safe = SafeDialKnob::init();
+        assert_eq!(safe.to_string(), "SafeDialKnob(pos=50, zeros=0)");
+
+        safe.apply_rotation_commands_solution_one(&sample_commands());
+        assert_eq!(format!("{safe}"), "SafeDialKnob(pos=32, zeros=3)");
+    }
+
+    #[test]
+    fn test_render_caret_under_current_position() {
+        let test_puzzle_input = sample_commands();
+        let mut safe = SafeDialKnob::init();
+        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
+
+        let rendered = safe.render();
+        let (ruler, caret_line) = rendered.split_once('\n').unwrap();
+        let caret_column = caret_line.find('^').unwrap();
+
+        assert_eq!(&ruler[caret_column - 1..=caret_column], "32");
+    }
+}
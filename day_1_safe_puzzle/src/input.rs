@@ -0,0 +1,546 @@
+use crate::command::{RotationCommand, RotationCommandParseError};
+use anyhow::{Context, bail};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::{Path, PathBuf};
+
+/// The ten-command example from the puzzle description, also stored in `assets/test_input`.
+pub const SAMPLE_INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+
+/// [`SAMPLE_INPUT`] parsed into commands.
+pub fn sample_commands() -> Vec<RotationCommand> {
+    parse_commands(SAMPLE_INPUT).expect("sample input is valid")
+}
+
+/// Parses a multiline string into commands, skipping blank lines.
+pub fn parse_commands(input: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    let mut converted: Vec<RotationCommand> = Vec::new();
+    parse_into(input, &mut converted)?;
+    Ok(converted)
+}
+
+/// Like [`parse_commands`], but clears and refills `out` so its capacity can be reused.
+pub fn parse_into(input: &str, out: &mut Vec<RotationCommand>) -> anyhow::Result<()> {
+    out.clear();
+    for (index, entry) in input.lines().enumerate() {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let element = RotationCommand::parse(entry).with_context(|| {
+            format!(
+                "line {}: failed to parse rotation command '{entry}'",
+                index + 1
+            )
+        })?;
+        out.push(element);
+    }
+    Ok(())
+}
+
+/// Best-effort parsing: keeps every command that parses and describes each line that was
+/// skipped, e.g. `line 2: skipped 'X1': invalid direction 'X' in 'X1'`. Blank lines are
+/// skipped silently, and zero-distance commands are kept with a warning.
+pub fn from_lines(lines: impl Iterator<Item = String>) -> (Vec<RotationCommand>, Vec<String>) {
+    let mut commands = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, entry) in lines.enumerate() {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        match RotationCommand::parse(&entry) {
+            Ok(command) => {
+                if command.turn_distance() == Some(0) {
+                    warnings.push(format!(
+                        "line {}: '{entry}' does not move the dial",
+                        index + 1
+                    ));
+                }
+                commands.push(command);
+            }
+            Err(e) => warnings.push(format!("line {}: skipped '{entry}': {e}", index + 1)),
+        }
+    }
+    (commands, warnings)
+}
+
+/// Lazily parses one command per line of `reader`, skipping blank lines. Nothing past the
+/// last command pulled from the iterator is read or parsed.
+pub fn commands_iter<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = anyhow::Result<RotationCommand>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            Ok(entry) if entry.trim().is_empty() => None,
+            Ok(entry) => Some(RotationCommand::parse(&entry).with_context(|| {
+                format!(
+                    "line {}: failed to parse rotation command '{entry}'",
+                    index + 1
+                )
+            })),
+            Err(e) => Some(Err(e).context("failed to read rotation commands")),
+        })
+}
+
+/// Loads `assets/<file_name>`, parsing every line with the lenient [`RotationCommand::parse`],
+/// so zero-distance commands such as `R0` are accepted. Lines starting with a digit are read
+/// in the distance-first form (`68L`) instead, so one file may mix both forms. Blank lines
+/// are skipped and an absolute path is used as-is.
+pub fn load_rotation_commands(file_name: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    let puzzle_input = read_input_file(input_path(file_name))?;
+    let mut converted: Vec<RotationCommand> = Vec::new();
+    for (index, entry) in puzzle_input.iter().enumerate() {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        converted.push(parse_file_line(file_name, index, entry)?);
+    }
+    Ok(converted)
+}
+
+/// Loads like [`load_rotation_commands`] but fails when the file holds no commands at all.
+pub fn load_rotation_commands_nonempty(file_name: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    let converted = load_rotation_commands(file_name)?;
+    if converted.is_empty() {
+        bail!("{file_name} holds no commands");
+    }
+    Ok(converted)
+}
+
+/// Loads like [`load_rotation_commands`] but fails as soon as the file turns out to hold more
+/// than `max_commands` commands, without reading or parsing the rest of it. Blank lines are
+/// skipped and do not count towards the limit.
+pub fn load_rotation_commands_limited(
+    file_name: &str,
+    max_commands: usize,
+) -> anyhow::Result<Vec<RotationCommand>> {
+    let mut converted: Vec<RotationCommand> = Vec::new();
+    for (index, line) in read_files_lines(input_path(file_name))?.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if converted.len() == max_commands {
+            bail!("{file_name} holds more than {max_commands} commands");
+        }
+        converted.push(parse_file_line(file_name, index, &line)?);
+    }
+    Ok(converted)
+}
+
+/// Parses `entry` like [`parse_either_form`], naming the file and line when it fails.
+fn parse_file_line(file_name: &str, index: usize, entry: &str) -> anyhow::Result<RotationCommand> {
+    parse_either_form(entry).with_context(|| {
+        format!(
+            "{file_name}:{}: failed to parse rotation command '{entry}'",
+            index + 1
+        )
+    })
+}
+
+/// Parses `entry` as a suffix command (`68L`, `+8R`) when it starts with a digit or a sign
+/// followed by one, otherwise as a prefix command (`L68`).
+fn parse_either_form(entry: &str) -> Result<RotationCommand, RotationCommandParseError> {
+    let entry_start = entry.trim_start();
+    if entry_start
+        .strip_prefix(['+', '-'])
+        .unwrap_or(entry_start)
+        .starts_with(|ch: char| ch.is_ascii_digit())
+    {
+        RotationCommand::parse_suffix(entry)
+    } else {
+        RotationCommand::parse(entry)
+    }
+}
+
+/// Loads each file like [`load_rotation_commands`] and concatenates the commands in order.
+pub fn load_rotation_commands_multi(files: &[&str]) -> anyhow::Result<Vec<RotationCommand>> {
+    let mut converted: Vec<RotationCommand> = Vec::new();
+    for file_name in files {
+        converted.extend(load_rotation_commands(file_name)?);
+    }
+    Ok(converted)
+}
+
+/// One line of a JSON Lines input, e.g. `{"direction":"R","distance":8}`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct JsonCommand {
+    direction: String,
+    distance: i32,
+}
+
+/// Loads `assets/<file_name>` holding one JSON object per line, skipping blank lines. An
+/// absolute path is used as-is.
+#[cfg(feature = "serde")]
+pub fn load_rotation_commands_jsonl(file_name: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    let puzzle_input = read_input_file(input_path(file_name))?;
+    let mut converted: Vec<RotationCommand> = Vec::new();
+    for (index, entry) in puzzle_input.iter().enumerate() {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        let element = parse_json_command(entry).with_context(|| {
+            format!(
+                "{file_name}:{}: failed to parse rotation command '{entry}'",
+                index + 1
+            )
+        })?;
+        converted.push(element);
+    }
+    Ok(converted)
+}
+
+#[cfg(feature = "serde")]
+fn parse_json_command(entry: &str) -> anyhow::Result<RotationCommand> {
+    let JsonCommand {
+        direction,
+        distance,
+    } = serde_json::from_str(entry)?;
+    let mut chars = direction.chars();
+    let (Some(dir_ch), None) = (chars.next(), chars.next()) else {
+        bail!("direction '{direction}' is not a single character");
+    };
+    Ok(RotationCommand {
+        direction: crate::command::Direction::try_from(dir_ch)?,
+        distance,
+    })
+}
+
+/// Downloads the puzzle input at `url` and parses it like [`parse_commands`]. A `session`
+/// token is sent as the `session` cookie the Advent of Code site expects.
+#[cfg(feature = "http")]
+pub fn fetch_rotation_commands(
+    url: &str,
+    session: Option<&str>,
+) -> anyhow::Result<Vec<RotationCommand>> {
+    let mut request = ureq::get(url);
+    if let Some(session) = session {
+        request = request.header("Cookie", format!("session={session}"));
+    }
+    let body = request
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .with_context(|| format!("failed to download input from {url}"))?;
+    parse_commands(&body).with_context(|| format!("failed to parse input from {url}"))
+}
+
+/// Outcome of parsing an input file without solving it.
+#[derive(Debug)]
+pub struct InputCheck {
+    pub commands: usize,
+    /// Every line that failed to parse, keyed by its 1-based line number.
+    pub errors: Vec<(usize, RotationCommandParseError)>,
+}
+
+impl InputCheck {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Parses every non-blank line of `assets/<file_name>` the way the loaders do, in either
+/// form, and collects all parse errors instead of stopping at the first one.
+pub fn check_rotation_commands(file_name: &str) -> anyhow::Result<InputCheck> {
+    let puzzle_input = read_input_file(input_path(file_name))?;
+    let mut check = InputCheck {
+        commands: 0,
+        errors: Vec::new(),
+    };
+    for (index, entry) in puzzle_input.iter().enumerate() {
+        if entry.trim().is_empty() {
+            continue;
+        }
+        match parse_either_form(entry) {
+            Ok(_) => check.commands += 1,
+            Err(e) => check.errors.push((index + 1, e)),
+        }
+    }
+    Ok(check)
+}
+
+fn read_input_file(input_path: PathBuf) -> anyhow::Result<Vec<String>> {
+    let lines = read_files_lines(input_path)?;
+    let mut puzzle_input: Vec<String> = Vec::new();
+    for line in lines {
+        puzzle_input.push(line?);
+    }
+    Ok(puzzle_input)
+}
+
+fn input_path(file_name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(file_name)
+}
+
+fn read_files_lines<P: AsRef<Path>>(filename: P) -> anyhow::Result<Lines<BufReader<File>>> {
+    let path = filename.as_ref();
+    let file = File::open(path)
+        .with_context(|| format!("failed to open input file {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+    skip_bom(&mut reader)
+        .with_context(|| format!("failed to read input file {}", path.display()))?;
+    Ok(reader.lines())
+}
+
+/// Consumes a leading UTF-8 byte order mark, which some Windows tools write and which would
+/// otherwise be read as the first line's direction.
+fn skip_bom<R: BufRead>(reader: &mut R) -> std::io::Result<()> {
+    const BOM: &[u8] = b"\xEF\xBB\xBF";
+    if reader.fill_buf()?.starts_with(BOM) {
+        reader.consume(BOM.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dial::SafeDialKnob;
+
+    #[test]
+    fn test_read_input() {
+        let test_puzzle_input = load_rotation_commands("test_input").unwrap();
+        assert!(!test_puzzle_input.is_empty());
+        assert_eq!(
+            load_rotation_commands("test_input_gaps").unwrap(),
+            test_puzzle_input
+        );
+    }
+
+    #[test]
+    fn test_from_lines_collects_warnings() {
+        let lines = ["L68", "X1", "", "R0", "R1a", "A0"].map(String::from);
+        let (commands, warnings) = from_lines(lines.into_iter());
+
+        let commands: Vec<String> = commands.iter().map(ToString::to_string).collect();
+        assert_eq!(commands, ["L68", "R0", "A0"]);
+        assert_eq!(
+            warnings,
+            [
+                "line 2: skipped 'X1': invalid direction 'X' in 'X1'",
+                "line 4: 'R0' does not move the dial",
+                "line 5: skipped 'R1a': invalid distance '1a' in 'R1a'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_mixed_prefix_and_suffix_forms() {
+        let mixed = load_rotation_commands("test_input_suffix").unwrap();
+        let prefix = load_rotation_commands("test_input").unwrap();
+
+        assert_eq!(mixed, prefix);
+    }
+
+    #[test]
+    fn test_load_skips_byte_order_mark() {
+        let path = std::env::temp_dir().join(format!("safe_puzzle_bom_{}", std::process::id()));
+        std::fs::write(&path, format!("\u{feff}{SAMPLE_INPUT}")).unwrap();
+
+        let commands = load_rotation_commands(&path.to_string_lossy());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(commands.unwrap(), sample_commands());
+    }
+
+    #[test]
+    fn test_load_rotation_commands_nonempty() {
+        assert!(
+            load_rotation_commands("test_input_blank")
+                .unwrap()
+                .is_empty()
+        );
+
+        let error = load_rotation_commands_nonempty("test_input_blank").unwrap_err();
+        assert_eq!(error.to_string(), "test_input_blank holds no commands");
+
+        assert_eq!(
+            load_rotation_commands_nonempty("test_input").unwrap().len(),
+            10
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_rotation_commands_jsonl() {
+        let from_jsonl: Vec<String> = load_rotation_commands_jsonl("test_input.jsonl")
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let from_constant: Vec<String> =
+            sample_commands().iter().map(ToString::to_string).collect();
+        assert_eq!(from_jsonl, from_constant);
+
+        assert!(parse_json_command(r#"{"direction":"RL","distance":8}"#).is_err());
+        assert!(parse_json_command(r#"{"direction":"X","distance":8}"#).is_err());
+        assert!(parse_json_command(r#"{"direction":"R"}"#).is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_fetch_rotation_commands_from_mock_server() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2025/day/1/input", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{SAMPLE_INPUT}",
+                SAMPLE_INPUT.len()
+            )
+            .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let commands = fetch_rotation_commands(&url, Some("abc123")).unwrap();
+        assert_eq!(commands.len(), 10);
+
+        let request = server.join().unwrap().to_ascii_lowercase();
+        assert!(request.contains("cookie: session=abc123"));
+    }
+
+    #[test]
+    fn test_load_rotation_commands_limited() {
+        let error = load_rotation_commands_limited("test_input", 5).unwrap_err();
+        assert_eq!(error.to_string(), "test_input holds more than 5 commands");
+
+        assert_eq!(
+            load_rotation_commands_limited("test_input", 10)
+                .unwrap()
+                .len(),
+            10
+        );
+        assert_eq!(
+            load_rotation_commands_limited("test_input_gaps", 10).unwrap(),
+            sample_commands()
+        );
+        assert!(
+            load_rotation_commands_limited("test_input_blank", 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_sample_input_matches_test_input_file() {
+        let from_file: Vec<String> = load_rotation_commands("test_input")
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let from_constant: Vec<String> =
+            sample_commands().iter().map(ToString::to_string).collect();
+
+        assert_eq!(from_file, from_constant);
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse_commands(SAMPLE_INPUT).unwrap().len(), 10);
+
+        let spaced = parse_commands("\nL68\n\n  R48  \n\n").unwrap();
+        assert_eq!(spaced.len(), 2);
+
+        let error = parse_commands("L68\nX1\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_commands_iter_is_lazy() {
+        let input = "L68\n\nL30\nR48\nnot a command\n";
+        let first: Vec<String> = commands_iter(input.as_bytes())
+            .take(3)
+            .map(|command| command.unwrap().to_string())
+            .collect();
+        assert_eq!(first, ["L68", "L30", "R48"]);
+
+        let error = commands_iter(input.as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap_err();
+        assert!(error.to_string().starts_with("line 5:"));
+
+        assert_eq!(commands_iter(SAMPLE_INPUT.as_bytes()).count(), 10);
+    }
+
+    #[test]
+    fn test_parse_into_reuses_buffer() {
+        let mut buffer = Vec::new();
+
+        parse_into(SAMPLE_INPUT, &mut buffer).unwrap();
+        assert_eq!(buffer.len(), 10);
+        let capacity = buffer.capacity();
+
+        parse_into("R8\nL3\n", &mut buffer).unwrap();
+        let parsed: Vec<String> = buffer.iter().map(ToString::to_string).collect();
+        assert_eq!(parsed, ["R8", "L3"]);
+        assert_eq!(buffer.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_sample_commands_answers() {
+        let commands = sample_commands();
+
+        assert_eq!(SafeDialKnob::count_zero_landings(&commands), 3);
+        assert_eq!(SafeDialKnob::count_zero_crossings(&commands).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_check_rotation_commands() {
+        let valid = check_rotation_commands("test_input").unwrap();
+        assert!(valid.is_valid());
+        assert_eq!(valid.commands, 10);
+
+        let invalid = check_rotation_commands("test_input_invalid").unwrap();
+        assert!(!invalid.is_valid());
+        assert_eq!(invalid.commands, 3);
+        let error_lines: Vec<usize> = invalid.errors.iter().map(|(line, _)| *line).collect();
+        assert_eq!(error_lines, [2, 4]);
+
+        assert_eq!(
+            parse_either_form("+8R").unwrap(),
+            RotationCommand::parse("R8").unwrap()
+        );
+        assert_eq!(parse_either_form(" -8L").unwrap().distance, -8);
+        assert!(parse_either_form("+R8").is_err());
+
+        let suffix = check_rotation_commands("test_input_suffix").unwrap();
+        assert!(suffix.is_valid());
+        assert_eq!(suffix.commands, 10);
+
+        let gaps = check_rotation_commands("test_input_gaps").unwrap();
+        assert!(gaps.is_valid());
+        assert_eq!(gaps.commands, 10);
+        assert!(
+            check_rotation_commands("test_input_blank")
+                .unwrap()
+                .is_valid()
+        );
+    }
+
+    #[test]
+    fn test_read_multiple_inputs() {
+        let combined =
+            load_rotation_commands_multi(&["test_input_first_half", "test_input_second_half"])
+                .unwrap();
+        let whole = sample_commands();
+
+        assert_eq!(combined.len(), 10);
+        assert_eq!(combined.len(), whole.len());
+
+        let with_blanks =
+            load_rotation_commands_multi(&["test_input_blank", "test_input_gaps"]).unwrap();
+        assert_eq!(with_blanks, whole);
+    }
+}
@@ -0,0 +1,45 @@
+mod analysis;
+mod binary;
+mod command;
+mod dial;
+mod input;
+mod multi;
+mod repl;
+mod summary;
+#[cfg(feature = "viz")]
+mod viz;
+
+pub use analysis::{
+    clicks, consecutive_duplicates, crossing_contexts, detect_cycle, direction_runs,
+    distinct_positions, extra_crossings, first_divergence, first_landing_on, first_visit_order,
+    fits_u16, full_loop_commands, max_distance, position_bounds, reverse_sequence, run_until,
+    run_until_first_zero, run_window, simulate, synthesize_for_count, total_clicks, total_distance,
+    trace_csv, unvisited_positions, walk, zero_crossing_clicks, zeros_in_click_range,
+};
+pub use binary::{read_commands_binary, write_commands_binary};
+pub use command::{
+    Direction, DirectionParseError, MAX_REPEAT, ParseOptions, RotationCommand,
+    RotationCommandParseError, render_diagnostic,
+};
+pub use dial::{
+    CountMode, DEFAULT_SIZE, DEFAULT_START, DialInteger, Orientation, Position, SafeDialKnob,
+    SafeDialKnobBuilder, ZeroCountPolicy, crossings_for, fast_forward, final_position,
+    position_at_click,
+};
+#[cfg(feature = "http")]
+pub use input::fetch_rotation_commands;
+#[cfg(feature = "serde")]
+pub use input::load_rotation_commands_jsonl;
+pub use input::{
+    InputCheck, SAMPLE_INPUT, check_rotation_commands, commands_iter, from_lines,
+    load_rotation_commands, load_rotation_commands_limited, load_rotation_commands_multi,
+    load_rotation_commands_nonempty, parse_commands, parse_into, sample_commands,
+};
+pub use multi::MultiDial;
+pub use repl::run_repl;
+pub use summary::{
+    OutputFormat, Summary, answers, load_report, solve, solve_many, solve_reversed, solve_str,
+    solve_timed,
+};
+#[cfg(feature = "viz")]
+pub use viz::to_svg;
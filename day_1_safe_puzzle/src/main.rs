@@ -1,198 +1,9 @@
 use anyhow::{Context, bail};
-use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
-use std::path::{Path, PathBuf};
-use thiserror::Error;
-
-#[derive(Debug, Error, PartialEq, Eq)]
-enum DirectionParseError {
-    #[error("unsupported direction '{0}'")]
-    Unsupported(char),
-}
-
-#[derive(Debug, Error)]
-enum RotationCommandParseError {
-    #[error("empty input")]
-    EmptyInput,
-
-    #[error("invalid direction '{dir}' in '{input}'")]
-    InvalidDirection {
-        input: String,
-        dir: char,
-        #[source]
-        source: DirectionParseError,
-    },
-
-    #[error("missing distance in '{input}'")]
-    MissingDistance { input: String },
-
-    #[error("invalid distance '{distance}' in '{input}'")]
-    InvalidDistance {
-        input: String,
-        distance: String,
-        #[source]
-        source: std::num::ParseIntError,
-    },
-}
-
-#[derive(Debug, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn get_direction_literal(&self) -> &'static str {
-        match self {
-            Direction::Left => "L",
-            Direction::Right => "R",
-        }
-    }
-}
-
-impl TryFrom<char> for Direction {
-    type Error = DirectionParseError;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'R' => Ok(Direction::Right),
-            'L' => Ok(Direction::Left),
-            other => Err(DirectionParseError::Unsupported(other)),
-        }
-    }
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.get_direction_literal())
-    }
-}
-
-#[derive(Debug)]
-struct RotationCommand {
-    direction: Direction,
-    distance: i32,
-}
-
-impl RotationCommand {
-    fn parse(input: &str) -> anyhow::Result<Self, RotationCommandParseError> {
-        let input = input.trim();
-        if input.is_empty() {
-            return Err(RotationCommandParseError::EmptyInput);
-        }
-        let mut chars = input.chars();
-        let dir_ch = chars.next().ok_or(RotationCommandParseError::EmptyInput)?;
-
-        let direction = Direction::try_from(dir_ch).map_err(|e| {
-            RotationCommandParseError::InvalidDirection {
-                input: input.to_string(),
-                dir: dir_ch,
-                source: e,
-            }
-        })?;
-
-        let distance_str = chars.as_str();
-        if distance_str.is_empty() {
-            return Err(RotationCommandParseError::MissingDistance {
-                input: input.to_string(),
-            });
-        }
-
-        let distance: i32 =
-            distance_str
-                .parse()
-                .map_err(|e| RotationCommandParseError::InvalidDistance {
-                    input: input.to_string(),
-                    distance: distance_str.to_string(),
-                    source: e,
-                })?;
-
-        Ok(Self {
-            direction,
-            distance,
-        })
-    }
-}
-
-impl Display for RotationCommand {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{}", self.direction, self.distance)
-    }
-}
-
-struct SafeDialKnob {
-    current_position: i32,
-    zero_position_occurrence: u32,
-}
-
-impl Default for SafeDialKnob {
-    fn default() -> Self {
-        SafeDialKnob {
-            current_position: 50,
-            zero_position_occurrence: 0,
-        }
-    }
-}
-
-impl SafeDialKnob {
-    fn init() -> Self {
-        SafeDialKnob::default()
-    }
-
-    fn rotate_knob_solution_two(&mut self, command: &RotationCommand) {
-        let mut current: i32 = self.current_position;
-        let direction = &command.direction;
-        let mut steps: i32 = command.distance;
-
-        while steps != 0 {
-            current = match direction {
-                Direction::Right => (current + 1) % 100,
-                Direction::Left => (current - 1) % 100,
-            };
-
-            if current == 0 {
-                self.zero_position_occurrence += 1;
-            }
-
-            steps -= 1;
-        }
-        self.current_position = current;
-    }
-
-    fn rotate_knob_solution_one(&mut self, command: &RotationCommand) {
-        let mut current: i32 = self.current_position;
-        let direction = &command.direction;
-        let steps: i32 = command.distance;
-
-        current = match direction {
-            Direction::Right => (current + steps) % 100,
-            Direction::Left => (current - steps) % 100,
-        };
-
-        if current == 0 {
-            self.zero_position_occurrence += 1;
-        }
-
-        self.current_position = current;
-    }
-
-    fn apply_rotation_commands_solution_two(&mut self, commands: &[RotationCommand]) {
-        commands
-            .iter()
-            .for_each(|command| self.rotate_knob_solution_two(command));
-    }
-
-    fn apply_rotation_commands_solution_one(&mut self, commands: &[RotationCommand]) {
-        commands
-            .iter()
-            .for_each(|command| self.rotate_knob_solution_one(command));
-    }
-
-    fn get_code_sequence(self) -> u32 {
-        self.zero_position_occurrence
-    }
-}
+use std::path::PathBuf;
+use task_1_safe_puzzle::{
+    OutputFormat, RotationCommand, RotationCommandParseError, check_rotation_commands, load_report,
+    load_rotation_commands_multi, render_diagnostic, run_repl, solve_timed,
+};
 
 /// --- Day 1: Secret Entrance ---
 ///
@@ -282,146 +93,182 @@ impl SafeDialKnob {
 ///
 /// Using password method 0x434C49434B, what is the password to open the door?
 fn main() -> anyhow::Result<()> {
-    let rotation_commands =
-        load_rotation_commands("puzzle_input").with_context(|| "failed in main")?;
+    let options = Options::parse(std::env::args().skip(1), std::env::var(INPUT_ENV_VAR).ok())?;
+
+    if options.repl {
+        return run_repl(std::io::stdin().lock(), &mut std::io::stdout());
+    }
+
+    if options.check {
+        return check_inputs(&options.inputs);
+    }
+
+    let rotation_commands = match &options.url {
+        Some(url) => fetch_input(url)?,
+        None => {
+            let inputs: Vec<&str> = options.inputs.iter().map(String::as_str).collect();
+            load_rotation_commands_multi(&inputs)
+                .inspect_err(print_parse_diagnostic)
+                .with_context(|| "failed in main")?
+        }
+    };
 
     if rotation_commands.is_empty() {
         bail!("no commands to execute");
     }
+    eprintln!("{}", load_report(&rotation_commands));
 
-    let mut safe_knob = SafeDialKnob::init();
-    safe_knob.apply_rotation_commands_solution_one(&rotation_commands);
+    let (summary, part_one_time, part_two_time) = solve_timed(&rotation_commands)?;
+    println!("{}", summary.render(options.format));
+    if options.timing {
+        eprintln!("part one took {part_one_time:?}, part two took {part_two_time:?}");
+    }
 
-    println!(
-        "The code for the fist puzzle, solution one is: {}",
-        safe_knob.get_code_sequence()
-    );
+    if let Some(output) = &options.output {
+        summary.write_to(output, options.format)?;
+    }
 
-    let mut safe_knob = SafeDialKnob::init();
-    safe_knob.apply_rotation_commands_solution_two(&rotation_commands);
+    Ok(())
+}
 
-    println!(
-        "The code for the first puzzle, solution two is: {}",
-        safe_knob.get_code_sequence()
-    );
+/// Downloads the input from `url`, authenticating with the session token in `AOC_SESSION`
+/// when it is set.
+#[cfg(feature = "http")]
+fn fetch_input(url: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    let session = std::env::var("AOC_SESSION").ok();
+    task_1_safe_puzzle::fetch_rotation_commands(url, session.as_deref())
+}
 
-    Ok(())
+#[cfg(not(feature = "http"))]
+fn fetch_input(url: &str) -> anyhow::Result<Vec<RotationCommand>> {
+    bail!("cannot fetch {url}: built without the http feature")
 }
 
-fn load_rotation_commands(file_name: &str) -> anyhow::Result<Vec<RotationCommand>> {
-    let puzzle_input = read_input_file(input_path(file_name))?;
-    let mut converted: Vec<RotationCommand> = Vec::new();
-    for entry in puzzle_input {
-        let element = RotationCommand::parse(&entry)
-            .with_context(|| format!("failed to parse rotation command '{entry}'"))?;
-        converted.push(element);
+/// Prints the offending line with a caret under the problem when `error` is a parse failure.
+fn print_parse_diagnostic(error: &anyhow::Error) {
+    let parse_error = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<RotationCommandParseError>());
+    if let Some(parse_error) = parse_error
+        && let Some(input) = parse_error.input()
+    {
+        eprintln!("{}", render_diagnostic(input, parse_error));
     }
-    Ok(converted)
 }
 
-fn read_input_file(input_path: PathBuf) -> anyhow::Result<Vec<String>> {
-    let lines = read_files_lines(input_path)?;
-    let mut puzzle_input: Vec<String> = Vec::new();
-    for line in lines {
-        puzzle_input.push(line?);
+fn check_inputs(inputs: &[String]) -> anyhow::Result<()> {
+    let mut failed = false;
+    for input in inputs {
+        let check = check_rotation_commands(input)?;
+        println!("{input}: {} commands parsed", check.commands);
+        for (line, error) in &check.errors {
+            println!("{input}:{line}: {error}");
+        }
+        failed |= !check.is_valid();
     }
-    Ok(puzzle_input)
+    if failed {
+        bail!("input check failed");
+    }
+    Ok(())
 }
 
-fn input_path(file_name: &str) -> PathBuf {
-    Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("assets")
-        .join(file_name)
+/// Environment variable naming the input file; it takes precedence over positional inputs.
+const INPUT_ENV_VAR: &str = "SAFE_PUZZLE_INPUT";
+
+struct Options {
+    format: OutputFormat,
+    output: Option<PathBuf>,
+    repl: bool,
+    check: bool,
+    timing: bool,
+    url: Option<String>,
+    inputs: Vec<String>,
 }
 
-fn read_files_lines<P: AsRef<Path>>(filename: P) -> anyhow::Result<Lines<BufReader<File>>> {
-    let path = filename.as_ref();
-    let file = File::open(path)
-        .with_context(|| format!("failed to open input file {}", path.display()))?;
-    Ok(BufReader::new(file).lines())
+impl Options {
+    /// Parses the command-line `args`. `env_input` is the value of [`INPUT_ENV_VAR`], which
+    /// replaces the positional inputs when set.
+    fn parse(
+        mut args: impl Iterator<Item = String>,
+        env_input: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let mut options = Options {
+            format: OutputFormat::Human,
+            output: None,
+            repl: false,
+            check: false,
+            timing: false,
+            url: None,
+            inputs: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--json" => options.format = OutputFormat::Json,
+                "--repl" => options.repl = true,
+                "--check" => options.check = true,
+                "--timing" => options.timing = true,
+                "--url" => options.url = Some(args.next().context("--url requires a URL")?),
+                "--output" => {
+                    let path = args.next().context("--output requires a path")?;
+                    options.output = Some(PathBuf::from(path));
+                }
+                flag if flag.starts_with("--") => bail!("unsupported argument '{flag}'"),
+                _ => options.inputs.push(arg),
+            }
+        }
+        if let Some(input) = env_input {
+            options.inputs = vec![input];
+        }
+        if options.inputs.is_empty() {
+            options.inputs.push("puzzle_input".to_string());
+        }
+        Ok(options)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_rotation_command_right_direction() {
-        let r = RotationCommand::parse("R12").unwrap();
-        assert_eq!(r.direction, Direction::Right);
-        assert_eq!(r.distance, 12);
-    }
-
-    #[test]
-    fn test_rotation_command_left_direction() {
-        let r = RotationCommand::parse("L21").unwrap();
-        assert_eq!(r.direction, Direction::Left);
-        assert_eq!(r.distance, 21);
-    }
-
-    #[test]
-    fn test_invalid_rotation_commands() {
-        assert!(matches!(
-            RotationCommand::parse("").unwrap_err(),
-            RotationCommandParseError::EmptyInput
-        ));
-        assert!(matches!(
-            RotationCommand::parse("X99").unwrap_err(),
-            RotationCommandParseError::InvalidDirection { .. }
-        ));
-        assert!(matches!(
-            RotationCommand::parse("R").unwrap_err(),
-            RotationCommandParseError::MissingDistance { .. }
-        ));
-        assert!(matches!(
-            RotationCommand::parse("Rabc").unwrap_err(),
-            RotationCommandParseError::InvalidDistance { .. }
-        ));
-    }
-
-    #[test]
-    fn test_read_input() {
-        let test_puzzle_input = load_rotation_commands("test_input").unwrap();
-        assert!(!test_puzzle_input.is_empty());
+    fn parse(args: &[&str], env_input: Option<&str>) -> Options {
+        Options::parse(
+            args.iter().map(ToString::to_string),
+            env_input.map(ToString::to_string),
+        )
+        .unwrap()
     }
 
     #[test]
-    fn test_solution_one_small_puzzle_input() {
-        let first_expected_answer = 3;
-        let test_puzzle_input = load_rotation_commands("test_input").unwrap();
-        let mut safe = SafeDialKnob::init();
-        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
-
-        assert_eq!(first_expected_answer, safe.get_code_sequence());
+    fn test_input_env_var_takes_precedence() {
+        assert_eq!(parse(&[], None).inputs, ["puzzle_input"]);
+        assert_eq!(parse(&["test_input"], None).inputs, ["test_input"]);
+        assert_eq!(parse(&[], Some("test_input")).inputs, ["test_input"]);
+        assert_eq!(
+            parse(&["--json", "puzzle_input"], Some("test_input")).inputs,
+            ["test_input"]
+        );
     }
 
     #[test]
-    fn test_solution_two_small_puzzle_input() {
-        let second_expected_answer = 6;
-        let test_puzzle_input = load_rotation_commands("test_input").unwrap();
-        let mut safe = SafeDialKnob::init();
-        safe.apply_rotation_commands_solution_two(&test_puzzle_input);
-
-        assert_eq!(second_expected_answer, safe.get_code_sequence());
+    fn test_url_option() {
+        assert_eq!(parse(&[], None).url, None);
+        let options = parse(&["--url", "http://localhost/input"], None);
+        assert_eq!(options.url.as_deref(), Some("http://localhost/input"));
+        assert!(Options::parse(["--url".to_string()].into_iter(), None).is_err());
     }
 
     #[test]
-    fn test_solution_one_puzzle_input() {
-        let first_star_answer = 1135;
-        let test_puzzle_input = load_rotation_commands("puzzle_input").unwrap();
-        let mut safe = SafeDialKnob::init();
-        safe.apply_rotation_commands_solution_one(&test_puzzle_input);
-
-        assert_eq!(first_star_answer, safe.get_code_sequence());
+    fn test_timing_flag() {
+        assert!(!parse(&[], None).timing);
+        assert!(parse(&["--timing", "test_input"], None).timing);
     }
 
     #[test]
-    fn test_solution_two_puzzle_input() {
-        let second_start_answer = 6558;
-        let test_puzzle_input = load_rotation_commands("puzzle_input").unwrap();
-        let mut safe = SafeDialKnob::init();
-        safe.apply_rotation_commands_solution_two(&test_puzzle_input);
+    fn test_input_env_var_changes_loaded_file() {
+        let options = parse(&[], Some("test_input"));
+        let inputs: Vec<&str> = options.inputs.iter().map(String::as_str).collect();
+        let commands = load_rotation_commands_multi(&inputs).unwrap();
 
-        assert_eq!(second_start_answer, safe.get_code_sequence());
+        assert_eq!(solve_timed(&commands).unwrap().0.part_one, 3);
     }
 }
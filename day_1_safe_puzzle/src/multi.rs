@@ -0,0 +1,81 @@
+use crate::command::RotationCommand;
+use crate::dial::{CountMode, SafeDialKnob};
+use anyhow::bail;
+
+/// Several dials, each driven by its own command stream. The combined password is the sum of
+/// their zero counts.
+#[derive(Debug, Clone, Default)]
+pub struct MultiDial {
+    dials: Vec<SafeDialKnob>,
+}
+
+impl MultiDial {
+    /// Takes the dials as built. Each one starts wherever it already points, so
+    /// `SafeDialKnob::init()` dials start at 50 and builder dials start at their own `start`.
+    pub fn new(dials: Vec<SafeDialKnob>) -> Self {
+        MultiDial { dials }
+    }
+
+    /// Applies `streams[i]` to the `i`-th dial and returns the summed zero count of all
+    /// dials. Fails without touching any dial when the number of streams does not match
+    /// the number of dials. With [`CountMode::Crossings`] a rotation that would overflow stops
+    /// the run with an error, leaving the dials where they had got to.
+    pub fn apply(
+        &mut self,
+        streams: &[Vec<RotationCommand>],
+        mode: CountMode,
+    ) -> anyhow::Result<u32> {
+        if streams.len() != self.dials.len() {
+            bail!(
+                "got {} command streams for {} dials",
+                streams.len(),
+                self.dials.len()
+            );
+        }
+        for (dial, commands) in self.dials.iter_mut().zip(streams) {
+            for command in commands {
+                match mode {
+                    CountMode::Landings => dial.rotate(command, mode),
+                    CountMode::Crossings => dial.rotate_knob_checked(command)?,
+                }
+            }
+        }
+        Ok(self.code())
+    }
+
+    /// Sum of the zero counts of every dial so far.
+    pub fn code(&self) -> u32 {
+        self.dials.iter().map(SafeDialKnob::zero_count).sum()
+    }
+
+    pub fn dials(&self) -> &[SafeDialKnob] {
+        &self.dials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{parse_commands, sample_commands};
+
+    #[test]
+    fn test_multi_dial_sums_individual_runs() {
+        let other = parse_commands("R50\nL100\nR1\n").unwrap();
+        let individual = SafeDialKnob::count_zero_crossings(&sample_commands()).unwrap()
+            + SafeDialKnob::count_zero_crossings(&other).unwrap();
+
+        let mut multi = MultiDial::new(vec![SafeDialKnob::init(), SafeDialKnob::init()]);
+        let code = multi
+            .apply(&[sample_commands(), other], CountMode::Crossings)
+            .unwrap();
+
+        assert_eq!(code, individual);
+        assert_eq!(code, 6 + 2);
+        assert_eq!(multi.dials()[1].current_position(), 1);
+        assert!(
+            multi
+                .apply(&[sample_commands()], CountMode::Crossings)
+                .is_err()
+        );
+    }
+}
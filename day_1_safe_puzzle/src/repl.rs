@@ -0,0 +1,58 @@
+use crate::command::RotationCommand;
+use crate::dial::SafeDialKnob;
+use std::io::{BufRead, Write};
+
+/// Reads one rotation command per line and applies it with solution-two rules, echoing the
+/// new position and the running zero count. Lines that fail to parse are reported and
+/// skipped, so a typo never ends the session.
+pub fn run_repl<R: BufRead, W: Write>(input: R, output: &mut W) -> anyhow::Result<()> {
+    let mut safe_knob = SafeDialKnob::init();
+    writeln!(output, "dial at {}", safe_knob.current_position())?;
+
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match RotationCommand::parse(&line) {
+            Ok(command) => match safe_knob.rotate_knob_checked(&command) {
+                Ok(()) => writeln!(
+                    output,
+                    "{command}: dial at {}, zeros {}",
+                    safe_knob.current_position(),
+                    safe_knob.zero_count()
+                )?,
+                Err(e) => writeln!(output, "error: {e:#}")?,
+            },
+            Err(e) => writeln!(output, "error: {e}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_repl_session() {
+        let script = Cursor::new("L68\nX9\n\nR48\nR-1\nR1000\n");
+        let mut output = Vec::new();
+        run_repl(script, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "dial at 50",
+                "L68: dial at 82, zeros 1",
+                "error: invalid direction 'X' in 'X9'",
+                "R48: dial at 30, zeros 2",
+                "error: negative distance in 'R-1' would overflow the click counter",
+                "R1000: dial at 30, zeros 12",
+            ]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::analysis::total_distance;
+use crate::command::RotationCommand;
+use crate::dial::SafeDialKnob;
+use crate::input::{load_rotation_commands, parse_commands};
+use anyhow::Context;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+/// Both puzzle answers for one input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub part_one: u32,
+    pub part_two: u32,
+}
+
+impl Summary {
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Human => self.to_string(),
+            OutputFormat::Json => format!(
+                "{{\"part_one\":{},\"part_two\":{}}}",
+                self.part_one, self.part_two
+            ),
+        }
+    }
+
+    /// Writes the rendered summary to `path`, creating or truncating the file.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P, format: OutputFormat) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, format!("{}\n", self.render(format)))
+            .with_context(|| format!("failed to write summary to {}", path.display()))
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "The code for the first puzzle, solution one is: {}",
+            self.part_one
+        )?;
+        write!(
+            f,
+            "The code for the first puzzle, solution two is: {}",
+            self.part_two
+        )
+    }
+}
+
+/// One-line sanity report of what was loaded, e.g. `loaded 10 commands, total distance 462`.
+pub fn load_report(commands: &[RotationCommand]) -> String {
+    format!(
+        "loaded {} commands, total distance {}",
+        commands.len(),
+        total_distance(commands)
+    )
+}
+
+/// Both answers for `commands`. Fails when a rotation would overflow the solution-two
+/// counters, e.g. on a negative distance.
+pub fn solve(commands: &[RotationCommand]) -> anyhow::Result<Summary> {
+    Ok(Summary {
+        part_one: SafeDialKnob::count_zero_landings(commands),
+        part_two: SafeDialKnob::count_zero_crossings(commands)?,
+    })
+}
+
+/// Solves the commands applied back to front. Unlike
+/// [`reverse_sequence`](crate::analysis::reverse_sequence) the directions are kept.
+pub fn solve_reversed(commands: &[RotationCommand]) -> anyhow::Result<Summary> {
+    let reversed: Vec<RotationCommand> = commands.iter().rev().copied().collect();
+    solve(&reversed)
+}
+
+/// Like [`solve`], also returning the wall-clock time each part took.
+pub fn solve_timed(commands: &[RotationCommand]) -> anyhow::Result<(Summary, Duration, Duration)> {
+    let started = Instant::now();
+    let part_one = SafeDialKnob::count_zero_landings(commands);
+    let part_one_time = started.elapsed();
+
+    let started = Instant::now();
+    let part_two = SafeDialKnob::count_zero_crossings(commands)?;
+    let part_two_time = started.elapsed();
+
+    Ok((Summary { part_one, part_two }, part_one_time, part_two_time))
+}
+
+/// Parses the whole puzzle input text and solves it in one step.
+pub fn solve_str(input: &str) -> anyhow::Result<Summary> {
+    solve(&parse_commands(input)?)
+}
+
+/// Loads and solves every file independently, so one bad file only fails its own entry.
+/// Paths are resolved like [`load_rotation_commands`] does. With the `rayon` feature the
+/// files are solved in parallel; results keep the order of `paths` either way.
+pub fn solve_many(paths: &[PathBuf]) -> Vec<anyhow::Result<Summary>> {
+    let solve_path =
+        |path: &PathBuf| load_rotation_commands(&path.to_string_lossy()).and_then(|c| solve(&c));
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        paths.par_iter().map(solve_path).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        paths.iter().map(solve_path).collect()
+    }
+}
+
+/// Both answers for the puzzle input text as `(part_one, part_two)`.
+pub fn answers(input: &str) -> anyhow::Result<(u32, u32)> {
+    solve_str(input).map(|summary| (summary.part_one, summary.part_two))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::sample_commands;
+
+    #[test]
+    fn test_load_report() {
+        assert_eq!(
+            load_report(&sample_commands()),
+            "loaded 10 commands, total distance 462"
+        );
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(answers(crate::input::SAMPLE_INPUT).unwrap(), (3, 6));
+        assert!(answers("R1a\n").is_err());
+        assert!(answers("R-5\n").is_err());
+    }
+
+    #[test]
+    fn test_solve_reversed() {
+        let forward = solve(&sample_commands()).unwrap();
+        let reversed = solve_reversed(&sample_commands()).unwrap();
+
+        assert_eq!(forward.part_one, 3);
+        assert_eq!(reversed.part_one, 1);
+    }
+
+    #[test]
+    fn test_solve_timed() {
+        let (summary, part_one_time, part_two_time) = solve_timed(&sample_commands()).unwrap();
+
+        assert_eq!(summary, solve(&sample_commands()).unwrap());
+        assert!(part_one_time >= Duration::ZERO);
+        assert!(part_two_time >= Duration::ZERO);
+    }
+
+    #[test]
+    fn test_solve_str() {
+        let summary = solve_str(crate::input::SAMPLE_INPUT).unwrap();
+        assert_eq!(summary.part_one, 3);
+        assert_eq!(summary.part_two, 6);
+
+        let error = solve_str("L68\nX1\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 2:"));
+
+        let error = solve_str("R-5").unwrap_err();
+        assert!(error.to_string().contains("negative distance in 'R-5'"));
+    }
+
+    #[test]
+    fn test_solve_many_isolates_failures() {
+        let paths = [
+            PathBuf::from("test_input"),
+            PathBuf::from("test_input_invalid"),
+        ];
+        let results = solve_many(&paths);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().part_two, 6);
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn test_write_summary_to_file() {
+        let test_puzzle_input = sample_commands();
+        let summary = solve(&test_puzzle_input).unwrap();
+        let path = std::env::temp_dir().join(format!("safe_puzzle_summary_{}", std::process::id()));
+
+        summary.write_to(&path, OutputFormat::Json).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"part_one\":3,\"part_two\":6}\n"
+        );
+
+        summary.write_to(&path, OutputFormat::Human).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "The code for the first puzzle, solution one is: 3\n\
+             The code for the first puzzle, solution two is: 6\n"
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,63 @@
+use crate::analysis::walk;
+use crate::command::RotationCommand;
+use crate::dial::{DEFAULT_SIZE, DEFAULT_START};
+use std::f64::consts::TAU;
+use std::fmt::Write;
+
+const CENTER: f64 = 120.0;
+const RADIUS: f64 = 100.0;
+
+/// Draws the default dial as an SVG: a circle with one tick per position, 0 at the top and
+/// numbers increasing clockwise, and a numbered dot at the resting position after each
+/// command. Dots for commands resting on the same position are drawn on top of each other.
+pub fn to_svg(commands: &[RotationCommand]) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\">\n\
+         <circle cx=\"{CENTER}\" cy=\"{CENTER}\" r=\"{RADIUS}\" fill=\"none\" stroke=\"black\"/>\n",
+        size = 2.0 * CENTER
+    );
+    for position in 0..DEFAULT_SIZE as i32 {
+        let (x1, y1) = point_on_dial(position, RADIUS);
+        let (x2, y2) = point_on_dial(position, RADIUS - 5.0);
+        writeln!(
+            svg,
+            "<line class=\"tick\" x1=\"{x1:.2}\" y1=\"{y1:.2}\" x2=\"{x2:.2}\" y2=\"{y2:.2}\" stroke=\"black\"/>"
+        )
+        .expect("writing to a String");
+    }
+    for (index, (_, position)) in walk(DEFAULT_START, commands).enumerate() {
+        let (x, y) = point_on_dial(position, RADIUS - 12.0);
+        writeln!(
+            svg,
+            "<circle class=\"position\" cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"3\" fill=\"red\"/>\n\
+             <text x=\"{x:.2}\" y=\"{y:.2}\" font-size=\"8\">{}</text>",
+            index + 1
+        )
+        .expect("writing to a String");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Coordinates of `position` on a circle of `radius` around the dial's centre.
+fn point_on_dial(position: i32, radius: f64) -> (f64, f64) {
+    let angle = f64::from(position) / f64::from(DEFAULT_SIZE) * TAU;
+    (CENTER + radius * angle.sin(), CENTER - radius * angle.cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::sample_commands;
+
+    #[test]
+    fn test_svg_marks_every_resting_position() {
+        let svg = to_svg(&sample_commands());
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("class=\"tick\"").count(), 100);
+        assert_eq!(svg.matches("class=\"position\"").count(), 10);
+        assert!(svg.contains(">10</text>"));
+    }
+}